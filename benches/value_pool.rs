@@ -0,0 +1,37 @@
+//! Builds the parameter dictionaries of a bulk import with and without a `ValuePool`.
+//! Run with `cargo bench --bench value_pool`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use seabolt::{Value, ValuePool};
+
+const ROWS: i64 = 1000;
+
+fn fresh(c: &mut Criterion) {
+    c.bench_function("fresh values", |b| {
+        b.iter(|| {
+            for i in 0..ROWS {
+                let id = Value::from_integer(i);
+                let name = Value::from_string("node");
+                black_box((id, name));
+            }
+        })
+    });
+}
+
+fn pooled(c: &mut Criterion) {
+    let mut pool = ValuePool::new(16);
+    c.bench_function("pooled values", |b| {
+        b.iter(|| {
+            for i in 0..ROWS {
+                let id = pool.take().into_integer(i);
+                let name = pool.take().into_string("node");
+                black_box((&id, &name));
+                pool.give(id);
+                pool.give(name);
+            }
+        })
+    });
+}
+
+criterion_group!(benches, fresh, pooled);
+criterion_main!(benches);
@@ -0,0 +1,34 @@
+use seabolt::{basic_auth, AccessMode, Address, Bolt, Config, Params, Value};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let bolt = Bolt::init()?;
+    let address = Address::new("localhost", "7687");
+    let auth = basic_auth("neo4j", "password", None);
+    let config = Config::build().finish();
+    let connector = bolt.create_connector(&address, &auth, &config);
+
+    let bookmark = {
+        let writer = connector.acquire(AccessMode::Write)?;
+        writer.with_transaction(|tx| {
+            let params = Params::new().insert("name", "Alice").build();
+            tx.run("CREATE (:Person {name: $name})", params)?
+                .consume()?;
+            Ok(())
+        })?;
+        writer.last_bookmark()
+    };
+
+    // Passing the write's bookmark makes the read wait until the server it lands on has
+    // caught up with that write.
+    let reader = connector.acquire(AccessMode::Read)?;
+    let bookmarks = bookmark.into_iter().map(Value::from).collect::<Vec<_>>();
+    reader.begin(Some(Params::new().insert("bookmarks", bookmarks).build()))?;
+    for record in reader.run("MATCH (p:Person) RETURN p.name AS name", Value::from_null())? {
+        if let Some(name) = record?.get("name") {
+            println!("{}", name.as_string());
+        }
+    }
+    reader.commit()?;
+
+    Ok(())
+}
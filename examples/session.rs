@@ -0,0 +1,40 @@
+use seabolt::{basic_auth, AccessMode, Address, Bolt, Config, Params, Value};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let bolt = Bolt::init()?;
+    let address = Address::new("localhost", "7687");
+    let auth = basic_auth("neo4j", "password", None);
+    let config = Config::build().finish();
+    let connector = bolt.create_connector(&address, &auth, &config);
+
+    let session = connector.session(None, AccessMode::Write)?;
+
+    let params = Params::new()
+        .insert("name", "Bob")
+        .insert("age", 42)
+        .build();
+    let summary = session
+        .run("CREATE (:Person {name: $name, age: $age})", params)?
+        .consume()?;
+    println!("created in {:?}", summary.result_available_after());
+
+    // A failing query leaves the session usable for the next one.
+    if let Err(e) = session.run("THIS IS NOT CYPHER", Value::from_null()) {
+        println!("query failed: {}", e);
+    }
+
+    let cursor = session.run(
+        "MATCH (p:Person) RETURN p.name AS name, p.age AS age",
+        Value::from_null(),
+    )?;
+    println!("columns: {:?}", cursor.fields());
+    for record in cursor {
+        let record = record?;
+        if let (Some(name), Some(age)) = (record.get("name"), record.get("age")) {
+            println!("{} is {}", name.as_string(), age.as_integer());
+        };
+    }
+
+    session.close()?;
+    Ok(())
+}
@@ -0,0 +1,49 @@
+//! `#[derive(FromRecord)]` for the `seabolt` crate; use it through seabolt's `derive` feature.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields};
+
+/// Implements `seabolt::FromRecord` for a struct with named fields, reading each field from
+/// the result column of the same name. `Option` fields accept null or missing columns.
+#[proc_macro_derive(FromRecord)]
+pub fn derive_from_record(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Error::new_spanned(&input, "FromRecord needs a struct with named fields")
+                    .to_compile_error()
+                    .into()
+            }
+        },
+        _ => {
+            return Error::new_spanned(&input, "FromRecord can only be derived for structs")
+                .to_compile_error()
+                .into()
+        }
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let reads = fields.iter().map(|f| {
+        let ident = f.ident.as_ref().unwrap();
+        let column = ident.to_string();
+        let column = column.strip_prefix("r#").unwrap_or(&column);
+        quote! { #ident: record.get_as(#column)? }
+    });
+
+    quote! {
+        impl #impl_generics ::seabolt::FromRecord for #name #ty_generics #where_clause {
+            fn from_record(
+                record: &::seabolt::Record,
+            ) -> ::std::result::Result<Self, ::seabolt::BoltError> {
+                ::std::result::Result::Ok(#name {
+                    #(#reads,)*
+                })
+            }
+        }
+    }
+    .into()
+}
@@ -1,11 +1,16 @@
 use std::{
     ffi::{CStr, CString},
+    fmt,
     marker::PhantomData,
+    mem,
     ops::Deref,
     ptr::NonNull,
     slice,
+    time::Duration,
 };
 
+use crate::BoltError;
+
 make_enum!(Scheme,
     Direct => seabolt_sys::BOLT_SCHEME_DIRECT,
     Routing => seabolt_sys::BOLT_SCHEME_ROUTING,
@@ -30,11 +35,19 @@ pub struct NTTWrapper<'a, T: NTTWrap> {
     a: PhantomData<&'a T>,
 }
 
-impl<'a, T: NTTWrap> NTTWrapper<'a, T>{
+impl<'a, T: NTTWrap> NTTWrapper<'a, T> {
+    // A wrapped type must be nothing but the pointer (`#[repr(transparent)]`), since the
+    // boxed pointer is read back as a `T`. Evaluated when `new` is instantiated, so a type
+    // that breaks this fails to build instead of panicking.
+    const SAME_LAYOUT: () = assert!(
+        mem::size_of::<T>() == mem::size_of::<*mut T::ptr>()
+            && mem::align_of::<T>() == mem::align_of::<*mut T::ptr>()
+    );
+
     pub fn new(ptr: NonNull<T::ptr>) -> Self {
-        assert_eq!(std::mem::size_of::<T>(),
-                   std::mem::size_of::<*mut T::ptr>());
-        
+        #[allow(clippy::let_unit_value)]
+        let () = Self::SAME_LAYOUT;
+
         let ptr = Box::into_raw(Box::new(ptr.as_ptr())) as *mut T;
         NTTWrapper {
             ptr,
@@ -57,7 +70,10 @@ impl<'a, T: NTTWrap> Drop for NTTWrapper<'a, T> {
     }
 }
 
-#[repr(C)]
+/// How the server's certificate is checked on an encrypted transport. seabolt only
+/// verifies the server: its TLS setup has no way to present a client certificate and key,
+/// so deployments that require mutual TLS cannot be reached through it.
+#[repr(transparent)]
 #[derive(Debug)]
 pub struct Trust {
     ptr: *mut seabolt_sys::BoltTrust,
@@ -143,16 +159,110 @@ impl TrustBuilder {
     }
 }
 
+#[repr(transparent)]
+#[derive(Debug)]
+pub struct SocketOptions {
+    ptr: *mut seabolt_sys::BoltSocketOptions,
+}
+
+impl SocketOptions {
+    pub fn build() -> SocketOptionsBuilder {
+        let ptr = unsafe { seabolt_sys::BoltSocketOptions_create() };
+        SocketOptionsBuilder {
+            inner: SocketOptions { ptr },
+        }
+    }
+
+    pub(crate) fn as_ptr(&self) -> *mut seabolt_sys::BoltSocketOptions {
+        self.ptr
+    }
+
+    pub fn connect_timeout(&self) -> Option<Duration> {
+        millis(unsafe { seabolt_sys::BoltSocketOptions_get_connect_timeout(self.ptr) })
+    }
+
+    pub fn keep_alive(&self) -> bool {
+        unsafe { seabolt_sys::BoltSocketOptions_get_keep_alive(self.ptr) == 1 }
+    }
+}
+
+impl Drop for SocketOptions {
+    fn drop(&mut self) {
+        unsafe { seabolt_sys::BoltSocketOptions_destroy(self.ptr) }
+    }
+}
+
+impl NTTWrap for SocketOptions {
+    type ptr = seabolt_sys::BoltSocketOptions;
+}
+
 #[derive(Debug)]
+pub struct SocketOptionsBuilder {
+    inner: SocketOptions,
+}
+
+impl SocketOptionsBuilder {
+    pub fn finish(self) -> SocketOptions {
+        self.inner
+    }
+
+    /// How long opening a new TCP (and TLS) connection to a server may take. The address
+    /// lookup before it is not covered, see `ConfigBuilder::with_connection_acquisition_timeout`
+    /// for how the two timeouts differ.
+    pub fn with_connect_timeout(self, timeout: Duration) -> Self {
+        unsafe {
+            seabolt_sys::BoltSocketOptions_set_connect_timeout(
+                self.inner.as_ptr(),
+                to_millis(timeout),
+            );
+        }
+        self
+    }
+
+    pub fn with_keep_alive(self, keep_alive: bool) -> Self {
+        unsafe {
+            seabolt_sys::BoltSocketOptions_set_keep_alive(
+                self.inner.as_ptr(),
+                if keep_alive { 1 } else { 0 },
+            );
+        }
+        self
+    }
+}
+
+// seabolt takes its timeouts as milliseconds in an i32, with 0 meaning no limit.
+fn millis(ms: i32) -> Option<Duration> {
+    if ms > 0 {
+        Some(Duration::from_millis(ms as u64))
+    } else {
+        None
+    }
+}
+
+// A nonzero duration under a millisecond is rounded up, as truncating it to 0 would turn a
+// very short limit into none at all.
+fn to_millis(d: Duration) -> i32 {
+    match d.as_millis() {
+        0 if d > Duration::ZERO => 1,
+        ms => ms.min(i32::MAX as u128) as i32,
+    }
+}
+
 pub struct Config {
     ptr: *mut seabolt_sys::BoltConfig,
+    // seabolt has no liveness check of its own, the connector does it on acquire.
+    liveness_check_timeout: Option<Duration>,
 }
 
 impl Config {
     pub fn build() -> ConfigBuilder {
         let ptr = unsafe { seabolt_sys::BoltConfig_create() };
         ConfigBuilder {
-            inner: Config { ptr },
+            inner: Config {
+                ptr,
+                liveness_check_timeout: None,
+            },
+            error: None,
         }
     }
 
@@ -181,6 +291,76 @@ impl Config {
             Some(unsafe { CStr::from_ptr(ptr) }.to_str().unwrap())
         }
     }
+
+    /// There is no `get_min_pool_size`: seabolt opens pooled connections only on demand and
+    /// has no setting for a number to keep open.
+    pub fn get_max_pool_size(&self) -> usize {
+        unsafe { seabolt_sys::BoltConfig_get_max_pool_size(self.ptr) as usize }
+    }
+
+    /// `None` when connections are kept regardless of age.
+    pub fn get_max_connection_lifetime(&self) -> Option<Duration> {
+        millis(unsafe { seabolt_sys::BoltConfig_get_max_connection_life_time(self.ptr) })
+    }
+
+    /// `None` when acquiring waits for a free connection indefinitely. The connect timeout
+    /// lives in `get_socket_options`.
+    pub fn get_connection_acquisition_timeout(&self) -> Option<Duration> {
+        millis(unsafe { seabolt_sys::BoltConfig_get_max_connection_acquisition_time(self.ptr) })
+    }
+
+    pub fn get_liveness_check_timeout(&self) -> Option<Duration> {
+        self.liveness_check_timeout
+    }
+
+    pub fn get_socket_options(&self) -> Option<NTTWrapper<SocketOptions>> {
+        let ptr = unsafe { seabolt_sys::BoltConfig_get_socket_options(self.ptr) };
+        NonNull::new(ptr).map(NTTWrapper::new)
+    }
+}
+
+// Certificates are summarised by their length so logging a config never dumps them.
+impl fmt::Debug for Config {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let trust = self.get_trust().map(|t| {
+            format!(
+                "Trust {{ certs: {} bytes, verification: {}, verify_hostname: {} }}",
+                t.certs().map_or(0, <[u8]>::len),
+                t.verification(),
+                t.verify_hostname()
+            )
+        });
+        let socket = self.get_socket_options().map(|o| {
+            format!(
+                "SocketOptions {{ connect_timeout: {:?}, keep_alive: {} }}",
+                o.connect_timeout(),
+                o.keep_alive()
+            )
+        });
+        f.debug_struct("Config")
+            .field("scheme", &self.get_scheme())
+            .field("transport", &self.get_transport())
+            .field("user_agent", &self.get_user_agent())
+            .field(
+                "trust",
+                &format_args!("{}", trust.as_deref().unwrap_or("None")),
+            )
+            .field("max_pool_size", &self.get_max_pool_size())
+            .field(
+                "max_connection_lifetime",
+                &self.get_max_connection_lifetime(),
+            )
+            .field(
+                "connection_acquisition_timeout",
+                &self.get_connection_acquisition_timeout(),
+            )
+            .field("liveness_check_timeout", &self.liveness_check_timeout)
+            .field(
+                "socket_options",
+                &format_args!("{}", socket.as_deref().unwrap_or("None")),
+            )
+            .finish()
+    }
 }
 
 impl Drop for Config {
@@ -192,27 +372,69 @@ impl Drop for Config {
 #[derive(Debug)]
 pub struct ConfigBuilder {
     inner: Config,
+    // The first setter that could not be applied, reported when the builder is finished.
+    error: Option<BoltError>,
 }
 
 impl ConfigBuilder {
+    /// Panics if a setter was given a value seabolt cannot represent, such as
+    /// `Scheme::Unknown`. Use `try_finish` to get that back as a `BoltError` instead, e.g.
+    /// when the scheme or transport comes from user input.
     pub fn finish(self) -> Config {
+        if let Some(e) = self.error {
+            panic!("invalid configuration: {}", e);
+        }
         self.inner
     }
 
-    pub fn with_scheme(self, scheme: Scheme) -> Self {
-        unsafe {
-            seabolt_sys::BoltConfig_set_scheme(self.inner.as_ptr(), scheme.as_idx() as i32);
+    /// Like `finish`, but returns `BoltError::UnknownVariant` rather than panicking for an
+    /// `Unknown` scheme or transport, and also rejects combinations seabolt would accept and
+    /// then fail on at connection time.
+    pub fn try_finish(self) -> Result<Config, BoltError> {
+        if let Some(e) = self.error {
+            return Err(e);
+        }
+        let config = self.inner;
+        let invalid = |reason: &str| {
+            Err(BoltError::InvalidConfig {
+                reason: reason.to_string(),
+            })
+        };
+        if config.get_transport() == Transport::Plaintext && config.get_trust().is_some() {
+            return invalid("trust settings only apply to an encrypted transport");
+        }
+        if config.get_max_pool_size() == 0 {
+            return invalid("the maximum pool size must be at least one");
+        }
+        Ok(config)
+    }
+
+    /// `Scheme::Unknown` is reported when the builder is finished, see `try_finish`.
+    pub fn with_scheme(mut self, scheme: Scheme) -> Self {
+        match scheme.as_idx() {
+            Ok(idx) => unsafe {
+                seabolt_sys::BoltConfig_set_scheme(self.inner.as_ptr(), idx as i32);
+            },
+            Err(e) => self.fail(e),
         }
         self
     }
 
-    pub fn with_transport(self, transport: Transport) -> Self {
-        unsafe {
-            seabolt_sys::BoltConfig_set_transport(self.inner.as_ptr(), transport.as_idx() as i32);
+    /// `Transport::Unknown` is reported when the builder is finished, see `try_finish`.
+    pub fn with_transport(mut self, transport: Transport) -> Self {
+        match transport.as_idx() {
+            Ok(idx) => unsafe {
+                seabolt_sys::BoltConfig_set_transport(self.inner.as_ptr(), idx as i32);
+            },
+            Err(e) => self.fail(e),
         }
         self
     }
 
+    fn fail(&mut self, e: BoltError) {
+        self.error.get_or_insert(e);
+    }
+
     pub fn with_trust(self, trust: Trust) -> Self {
         unsafe {
             seabolt_sys::BoltConfig_set_trust(self.inner.as_ptr(), trust.as_ptr());
@@ -227,4 +449,122 @@ impl ConfigBuilder {
         }
         self
     }
+
+    /// Caps the connections the pool keeps per server. seabolt has no minimum to go with it;
+    /// connections are only opened as they are acquired.
+    pub fn with_max_pool_size(self, size: usize) -> Self {
+        unsafe {
+            seabolt_sys::BoltConfig_set_max_pool_size(
+                self.inner.as_ptr(),
+                size.min(i32::MAX as usize) as i32,
+            );
+        }
+        self
+    }
+
+    pub fn with_max_connection_lifetime(self, lifetime: Duration) -> Self {
+        unsafe {
+            seabolt_sys::BoltConfig_set_max_connection_life_time(
+                self.inner.as_ptr(),
+                to_millis(lifetime),
+            );
+        }
+        self
+    }
+
+    /// How long `Connector::acquire` waits for the pool to hand out a connection when all
+    /// of them are in use. Establishing a new connection is bounded separately by
+    /// `SocketOptionsBuilder::with_connect_timeout`.
+    ///
+    /// A slow DNS lookup is bounded by neither: seabolt resolves the address with a blocking
+    /// `getaddrinfo` before the connect timeout starts, and this timeout only covers waiting
+    /// for a pooled connection to free up, so only the system resolver's own timeout applies.
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use seabolt::{config::SocketOptions, Config};
+    ///
+    /// let config = Config::build()
+    ///     // Fail when all pooled connections are still busy after five seconds...
+    ///     .with_connection_acquisition_timeout(Duration::from_secs(5))
+    ///     // ...or when a server has not accepted a new connection within one.
+    ///     .with_socket_options(
+    ///         SocketOptions::build()
+    ///             .with_connect_timeout(Duration::from_secs(1))
+    ///             .finish(),
+    ///     )
+    ///     .finish();
+    /// let acquisition = config.get_connection_acquisition_timeout();
+    /// assert_eq!(acquisition, Some(Duration::from_secs(5)));
+    /// let connect = config.get_socket_options().unwrap().connect_timeout();
+    /// assert_eq!(connect, Some(Duration::from_secs(1)));
+    /// ```
+    pub fn with_connection_acquisition_timeout(self, timeout: Duration) -> Self {
+        unsafe {
+            seabolt_sys::BoltConfig_set_max_connection_acquisition_time(
+                self.inner.as_ptr(),
+                to_millis(timeout),
+            );
+        }
+        self
+    }
+
+    /// Connections idle in the pool for longer than `timeout` are sent a RESET before being
+    /// handed out, so one silently dropped by a firewall is replaced rather than returned.
+    pub fn with_liveness_check_timeout(mut self, timeout: Duration) -> Self {
+        self.inner.liveness_check_timeout = Some(timeout);
+        self
+    }
+
+    pub fn with_socket_options(self, options: SocketOptions) -> Self {
+        unsafe {
+            seabolt_sys::BoltConfig_set_socket_options(self.inner.as_ptr(), options.as_ptr());
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_variants_are_errors() {
+        let err = |b: ConfigBuilder| b.try_finish().unwrap_err();
+        assert_eq!(
+            err(Config::build().with_scheme(Scheme::Unknown)),
+            BoltError::UnknownVariant { name: "Scheme" }
+        );
+        assert_eq!(
+            err(Config::build().with_transport(Transport::Unknown)),
+            BoltError::UnknownVariant { name: "Transport" }
+        );
+    }
+
+    #[test]
+    fn sub_millisecond_timeouts_round_up() {
+        assert_eq!(to_millis(Duration::ZERO), 0);
+        assert_eq!(to_millis(Duration::from_nanos(1)), 1);
+        assert_eq!(to_millis(Duration::from_micros(1500)), 1);
+        assert_eq!(to_millis(Duration::from_secs(u64::MAX)), i32::MAX);
+
+        let config = Config::build()
+            .with_connection_acquisition_timeout(Duration::from_micros(500))
+            .with_socket_options(
+                SocketOptions::build()
+                    .with_connect_timeout(Duration::from_micros(500))
+                    .finish(),
+            )
+            .finish();
+        let ms = Some(Duration::from_millis(1));
+        assert_eq!(config.get_connection_acquisition_timeout(), ms);
+        assert_eq!(config.get_socket_options().unwrap().connect_timeout(), ms);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid configuration")]
+    fn finish_panics_on_unknown_variants() {
+        Config::build().with_scheme(Scheme::Unknown).finish();
+    }
 }
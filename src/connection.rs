@@ -0,0 +1,787 @@
+use std::{
+    cell::Cell,
+    collections::HashMap,
+    ffi::CStr,
+    mem,
+    ops::Deref,
+    os::raw::c_char,
+    ptr::{self, NonNull},
+    rc::Rc,
+    sync::Arc,
+    time::Instant,
+};
+
+use crate::{
+    config::NTTWrapper, trace::QueryTrace, Address, BoltError, Bookmark, Connector, Summary,
+    Value, ValueRef, ValueType,
+};
+
+make_enum!(AccessMode,
+    Write => seabolt_sys::BOLT_ACCESS_MODE_WRITE,
+    Read => seabolt_sys::BOLT_ACCESS_MODE_READ,
+);
+
+impl Default for AccessMode {
+    fn default() -> Self {
+        AccessMode::Write
+    }
+}
+
+struct MetadataSetters {
+    bookmarks:
+        unsafe extern "C" fn(*mut seabolt_sys::BoltConnection, *mut seabolt_sys::BoltValue) -> i32,
+    tx_timeout: unsafe extern "C" fn(*mut seabolt_sys::BoltConnection, i64) -> i32,
+    tx_metadata:
+        unsafe extern "C" fn(*mut seabolt_sys::BoltConnection, *mut seabolt_sys::BoltValue) -> i32,
+}
+
+const BEGIN_METADATA: MetadataSetters = MetadataSetters {
+    bookmarks: seabolt_sys::BoltConnection_set_begin_bookmarks,
+    tx_timeout: seabolt_sys::BoltConnection_set_begin_tx_timeout,
+    tx_metadata: seabolt_sys::BoltConnection_set_begin_tx_metadata,
+};
+
+const RUN_METADATA: MetadataSetters = MetadataSetters {
+    bookmarks: seabolt_sys::BoltConnection_set_run_bookmarks,
+    tx_timeout: seabolt_sys::BoltConnection_set_run_tx_timeout,
+    tx_metadata: seabolt_sys::BoltConnection_set_run_tx_metadata,
+};
+
+// The connector a connection goes back to: borrowed by `Connector::acquire`, or shared for
+// the `'static` connections that `Connector::acquire_owned` hands out.
+#[derive(Debug)]
+enum Pool<'a> {
+    Borrowed(&'a Connector),
+    Shared(Arc<Connector>),
+}
+
+impl<'a> Deref for Pool<'a> {
+    type Target = Connector;
+
+    fn deref(&self) -> &Connector {
+        match self {
+            Pool::Borrowed(connector) => connector,
+            Pool::Shared(connector) => connector,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Connection<'a> {
+    ptr: *mut seabolt_sys::BoltConnection,
+    connector: Pool<'a>,
+}
+
+impl<'a> Connection<'a> {
+    pub(crate) fn new(ptr: *mut seabolt_sys::BoltConnection, connector: &'a Connector) -> Self {
+        Connection {
+            ptr,
+            connector: Pool::Borrowed(connector),
+        }
+    }
+
+    // Swaps the borrow of the connector for a share of it; `connector` must be the one the
+    // connection came from.
+    pub(crate) fn into_shared(mut self, connector: Arc<Connector>) -> Connection<'static> {
+        debug_assert!(ptr::eq(&*self.connector, &*connector));
+        let ptr = mem::replace(&mut self.ptr, ptr::null_mut());
+        Connection {
+            ptr,
+            connector: Pool::Shared(connector),
+        }
+    }
+
+    pub fn run(&self, cypher: &str, params: Value) -> Result<Records<'_>, BoltError> {
+        self.run_with_metadata(cypher, params, None)
+    }
+
+    /// Runs an auto-commit query, `metadata` takes the same keys as for `begin`.
+    pub fn run_with_metadata(
+        &self,
+        cypher: &str,
+        params: Value,
+        metadata: Option<Value>,
+    ) -> Result<Records<'_>, BoltError> {
+        let trace = QueryTrace::start(&self.connector, cypher, &params);
+        let (run, pull) = self.load_query(cypher, &params, metadata.as_ref())?;
+        self.send()?;
+        self.start_records(trace, run, pull)
+    }
+
+    /// Runs a query that should return exactly one row, such as `RETURN count(*)`, failing
+    /// with `BoltError::NoRecords` or `BoltError::MultipleRecords` otherwise. The server
+    /// still sends every row, as Bolt v1 to v3 cannot pull part of a result; any after the
+    /// second are read and thrown away, leaving the connection ready for the next query.
+    /// See `run_first` for when extra rows are fine.
+    pub fn run_single(&self, cypher: &str, params: Value) -> Result<Record, BoltError> {
+        let mut records = self.run(cypher, params)?;
+        let record = records.next().ok_or(BoltError::NoRecords)??;
+        match records.next() {
+            None => Ok(record),
+            Some(other) => {
+                other?;
+                Err(BoltError::MultipleRecords)
+            }
+        }
+    }
+
+    /// The first row of a query, if any. The rest are still sent by the server, and are read
+    /// and thrown away.
+    pub fn run_first(&self, cypher: &str, params: Value) -> Result<Option<Record>, BoltError> {
+        let mut records = self.run(cypher, params)?;
+        records.next().transpose()
+    }
+
+    /// Like `run`, but gives up on the rows once `deadline` has passed: the query is
+    /// abandoned with a RESET and the records fail with `BoltError::Timeout`.
+    ///
+    /// This is not a hard deadline. It is only checked before the query is sent and
+    /// between rows, so the RUN round trip, the final summary and any single row read that
+    /// blocks can all run past it. seabolt sets no receive timeout on its sockets and has
+    /// no way to set one, so a stalled read blocks until TCP keep-alive
+    /// (`SocketOptions::keep_alive`) notices the peer has gone away. Enforce a hard limit
+    /// server-side with the `tx_timeout` metadata.
+    pub fn run_with_row_deadline(
+        &self,
+        cypher: &str,
+        params: Value,
+        deadline: Instant,
+    ) -> Result<Records<'_>, BoltError> {
+        if Instant::now() >= deadline {
+            return Err(BoltError::Timeout);
+        }
+        let mut records = self.run(cypher, params)?;
+        records.deadline = Some(deadline);
+        Ok(records)
+    }
+
+    /// Runs a query and has the server throw its rows away, only the summary comes back.
+    /// Rows can only be discarded this way, as every other `run*` loads its PULL_ALL along
+    /// with the RUN, leaving no stream open to discard afterwards.
+    pub fn run_discard(&self, cypher: &str, params: Value) -> Result<Summary, BoltError> {
+        let trace = QueryTrace::start(&self.connector, cypher, &params);
+        self.load_run(cypher, &params, None)?;
+        let run = self.last_request();
+        let discard = self.load_discard()?;
+        self.send()?;
+        let res = trace.in_scope(|| {
+            if let Err(e) = self.fetch_summary(run) {
+                let _ = self.fetch_summary(discard);
+                return Err(e);
+            }
+            let run_metadata = self.metadata();
+            self.fetch_summary(discard)?;
+            Ok(Summary::new(run_metadata?, self.metadata()?))
+        });
+        trace.finish(res.as_ref().err());
+        res
+    }
+
+    // -1 discards every row, the only amount Bolt v1 to v3 can ask for.
+    fn load_discard(&self) -> Result<seabolt_sys::BoltRequest, BoltError> {
+        self.check(unsafe { seabolt_sys::BoltConnection_load_discard_request(self.ptr, -1) })?;
+        Ok(self.last_request())
+    }
+
+    /// Loads every query before a single send. The handles must be turned into records in
+    /// the order they were returned, and each stream finished (or dropped) before the next
+    /// one is started, otherwise `BoltError::PipelineOrder` is returned.
+    ///
+    /// Every query's parameters are checked before any is loaded, so a bad one fails the
+    /// call without sending anything. Should seabolt still fail to load a query after that
+    /// (it ran out of memory), the ones before it have already been loaded and cannot be
+    /// taken back: they are sent and their results thrown away, and the connection reset,
+    /// before the error is returned.
+    pub fn run_pipeline(
+        &self,
+        queries: &[(&str, Value)],
+    ) -> Result<Vec<QueryHandle<'_>>, BoltError> {
+        for (_, params) in queries {
+            param_count(params)?;
+        }
+        let mut requests = Vec::with_capacity(queries.len());
+        for (cypher, params) in queries {
+            let trace = QueryTrace::start(&self.connector, cypher, params);
+            match self.load_query(cypher, params, None) {
+                Ok((run, pull)) => requests.push((trace, run, pull)),
+                Err(e) => {
+                    trace.finish(Some(&e));
+                    self.abandon(&requests, &e);
+                    return Err(e);
+                }
+            }
+        }
+        self.send()?;
+        let position = Rc::new(Cell::new(0));
+        Ok(requests
+            .into_iter()
+            .enumerate()
+            .map(|(index, (trace, run, pull))| QueryHandle {
+                conn: self,
+                trace: Some(trace),
+                run,
+                pull,
+                index,
+                position: position.clone(),
+                started: false,
+            })
+            .collect())
+    }
+
+    // Sends the requests loaded so far for a pipeline that failed part way and throws
+    // their results away, so they cannot go out unnoticed with the next query.
+    fn abandon(
+        &self,
+        requests: &[(
+            QueryTrace,
+            seabolt_sys::BoltRequest,
+            seabolt_sys::BoltRequest,
+        )],
+        e: &BoltError,
+    ) {
+        if self.send().is_ok() {
+            for (trace, run, pull) in requests {
+                let _ = self.fetch_summary(*run);
+                let _ = self.fetch_summary(*pull);
+                trace.finish(Some(e));
+            }
+        }
+        let _ = self.reset();
+    }
+
+    fn load_query(
+        &self,
+        cypher: &str,
+        params: &Value,
+        metadata: Option<&Value>,
+    ) -> Result<(seabolt_sys::BoltRequest, seabolt_sys::BoltRequest), BoltError> {
+        self.load_run(cypher, params, metadata)?;
+        let run = self.last_request();
+        // -1 pulls every row, the only amount Bolt v1 to v3 can ask for.
+        self.check(unsafe { seabolt_sys::BoltConnection_load_pull_request(self.ptr, -1) })?;
+        Ok((run, self.last_request()))
+    }
+
+    fn start_records(
+        &self,
+        trace: QueryTrace,
+        run: seabolt_sys::BoltRequest,
+        pull: seabolt_sys::BoltRequest,
+    ) -> Result<Records<'_>, BoltError> {
+        let run_metadata = match trace.in_scope(|| {
+            self.fetch_summary(run)?;
+            self.metadata()
+        }) {
+            Ok(run_metadata) => run_metadata,
+            Err(e) => {
+                let _ = self.fetch_summary(pull);
+                trace.finish(Some(&e));
+                return Err(e);
+            }
+        };
+        let fields = self.field_names();
+        Ok(Records::new(self, pull, fields, run_metadata, trace))
+    }
+
+    /// Returns the connection to the pool now, resetting it first if a failed request left
+    /// it unusable. The connection is released even when that reset fails.
+    pub fn close(mut self) -> Result<(), BoltError> {
+        self.release()
+    }
+
+    fn release(&mut self) -> Result<(), BoltError> {
+        let res = self.reset_if_failed();
+        self.connector.release(self.ptr);
+        self.ptr = ptr::null_mut();
+        res
+    }
+
+    pub(crate) fn reset_if_failed(&self) -> Result<(), BoltError> {
+        let state = unsafe {
+            seabolt_sys::BoltStatus_get_state(seabolt_sys::BoltConnection_status(self.ptr))
+        };
+        if state as u32 == seabolt_sys::BOLT_CONNECTION_STATE_FAILED {
+            self.reset()
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn reset(&self) -> Result<(), BoltError> {
+        self.roundtrip(unsafe { seabolt_sys::BoltConnection_load_reset_request(self.ptr) })
+    }
+
+    /// Checks the server is still answering by running `RETURN 1`. Unlike a RESET this
+    /// leaves an open transaction alone, running inside it, and it takes `&mut self` so no
+    /// result stream can still be open to have its rows read out from under it.
+    pub fn ping(&mut self) -> Result<(), BoltError> {
+        self.run_single("RETURN 1", Value::from_null()).map(|_| ())
+    }
+
+    /// The agent string the server sent in its handshake, e.g. "Neo4j/4.0.0".
+    pub fn server_agent(&self) -> Option<&str> {
+        let ptr = unsafe { seabolt_sys::BoltConnection_server(self.ptr) };
+        if ptr.is_null() {
+            None
+        } else {
+            unsafe { CStr::from_ptr(ptr) }.to_str().ok()
+        }
+    }
+
+    /// The resolved endpoint this connection is talking to.
+    pub fn server_address(&self) -> Option<NTTWrapper<'_, Address>> {
+        let ptr = unsafe { seabolt_sys::BoltConnection_remote_endpoint(self.ptr) };
+        NonNull::new(ptr as *mut _).map(NTTWrapper::new)
+    }
+
+    /// `metadata` is a dictionary that may carry `bookmarks` (a list of strings),
+    /// `tx_timeout` (milliseconds) and `tx_metadata` (a dictionary).
+    pub fn begin(&self, metadata: Option<Value>) -> Result<(), BoltError> {
+        self.check(unsafe { seabolt_sys::BoltConnection_clear_begin(self.ptr) })?;
+        self.set_metadata(metadata.as_ref(), &BEGIN_METADATA)?;
+        self.roundtrip(unsafe { seabolt_sys::BoltConnection_load_begin_request(self.ptr) })
+    }
+
+    pub fn commit(&self) -> Result<(), BoltError> {
+        self.roundtrip(unsafe { seabolt_sys::BoltConnection_load_commit_request(self.ptr) })
+    }
+
+    pub fn rollback(&self) -> Result<(), BoltError> {
+        self.roundtrip(unsafe { seabolt_sys::BoltConnection_load_rollback_request(self.ptr) })
+    }
+
+    fn roundtrip(&self, rc: i32) -> Result<(), BoltError> {
+        self.check(rc)?;
+        let request = self.last_request();
+        self.send()?;
+        self.fetch_summary(request)
+    }
+
+    pub fn last_bookmark(&self) -> Option<Bookmark> {
+        let p = unsafe { seabolt_sys::BoltConnection_last_bookmark(self.ptr) };
+        if p.is_null() {
+            None
+        } else {
+            let s = unsafe { CStr::from_ptr(p) }.to_string_lossy();
+            Some(Bookmark::new(s.into_owned()))
+        }
+    }
+
+    fn set_metadata(
+        &self,
+        metadata: Option<&Value>,
+        setters: &MetadataSetters,
+    ) -> Result<(), BoltError> {
+        let metadata = match metadata {
+            Some(metadata) => metadata,
+            None => return Ok(()),
+        };
+        metadata.expect_type(ValueType::Dictionary)?;
+        if let Some(bookmarks) = metadata.get("bookmarks") {
+            bookmarks.expect_type(ValueType::List)?;
+            self.check(unsafe { (setters.bookmarks)(self.ptr, bookmarks.as_ptr()) })?;
+        }
+        if let Some(timeout) = metadata.get("tx_timeout") {
+            timeout.expect_type(ValueType::Integer)?;
+            self.check(unsafe { (setters.tx_timeout)(self.ptr, timeout.as_integer()) })?;
+        }
+        if let Some(tx_metadata) = metadata.get("tx_metadata") {
+            tx_metadata.expect_type(ValueType::Dictionary)?;
+            self.check(unsafe { (setters.tx_metadata)(self.ptr, tx_metadata.as_ptr()) })?;
+        }
+        Ok(())
+    }
+
+    fn load_run(
+        &self,
+        cypher: &str,
+        params: &Value,
+        metadata: Option<&Value>,
+    ) -> Result<(), BoltError> {
+        let n = param_count(params)?;
+        self.check(unsafe { seabolt_sys::BoltConnection_clear_run(self.ptr) })?;
+        self.set_metadata(metadata, &RUN_METADATA)?;
+        self.check(unsafe {
+            seabolt_sys::BoltConnection_set_run_cypher(
+                self.ptr,
+                cypher.as_ptr() as *const c_char,
+                cypher.len() as u64,
+                n,
+            )
+        })?;
+        for i in 0..n {
+            let key = params.key_bytes(i);
+            let p = unsafe {
+                seabolt_sys::BoltConnection_set_run_cypher_parameter(
+                    self.ptr,
+                    i,
+                    key.as_ptr() as *const c_char,
+                    key.len() as u64,
+                )
+            };
+            params.dict_value(i as usize).unwrap().copy_to(p)?;
+        }
+        self.check(unsafe { seabolt_sys::BoltConnection_load_run_request(self.ptr) })
+    }
+
+    fn field_names(&self) -> Arc<[String]> {
+        let p = unsafe { seabolt_sys::BoltConnection_field_names(self.ptr) };
+        match NonNull::new(p).map(NTTWrapper::<Value>::new) {
+            Some(names) if names.get_type() == ValueType::List => (0..names.len())
+                .filter_map(|i| names.list_get(i).map(|v| v.as_string().to_string()))
+                .collect(),
+            _ => Arc::new([]),
+        }
+    }
+
+    pub(crate) fn last_request(&self) -> seabolt_sys::BoltRequest {
+        unsafe { seabolt_sys::BoltConnection_last_request(self.ptr) }
+    }
+
+    pub(crate) fn send(&self) -> Result<(), BoltError> {
+        if unsafe { seabolt_sys::BoltConnection_send(self.ptr) } < 0 {
+            Err(self.error())
+        } else {
+            Ok(())
+        }
+    }
+
+    pub(crate) fn fetch_summary(&self, request: seabolt_sys::BoltRequest) -> Result<(), BoltError> {
+        if unsafe { seabolt_sys::BoltConnection_fetch_summary(self.ptr, request) } < 0
+            || unsafe { seabolt_sys::BoltConnection_summary_success(self.ptr) } != 1
+        {
+            Err(self.error())
+        } else {
+            Ok(())
+        }
+    }
+
+    pub(crate) fn metadata(&self) -> Result<Value, BoltError> {
+        unsafe { Value::duplicate(seabolt_sys::BoltConnection_metadata(self.ptr)) }
+            .ok_or(BoltError::OutOfMemory)
+    }
+
+    pub(crate) fn check(&self, rc: i32) -> Result<(), BoltError> {
+        if rc == 0 {
+            Ok(())
+        } else {
+            Err(self.error())
+        }
+    }
+
+    pub(crate) fn error(&self) -> BoltError {
+        let failure = unsafe { seabolt_sys::BoltConnection_failure(self.ptr) };
+        match NonNull::new(failure).map(NTTWrapper::<Value>::new) {
+            Some(failure) if failure.get_type() == ValueType::Dictionary => {
+                BoltError::from_failure(&failure)
+            }
+            _ => unsafe { BoltError::from_status(seabolt_sys::BoltConnection_status(self.ptr)) },
+        }
+    }
+}
+
+// How many parameters RUN is given, failing for anything that is not a dictionary or null.
+fn param_count(params: &Value) -> Result<i32, BoltError> {
+    match params.get_type() {
+        ValueType::Null => Ok(0),
+        ValueType::Dictionary => Ok(params.len() as i32),
+        found => Err(BoltError::TypeMismatch {
+            expected: ValueType::Dictionary,
+            found,
+        }),
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl Connection<'static> {
+    /// Runs the query and collects every record on tokio's blocking thread pool. The Bolt
+    /// IO itself is still synchronous, this only keeps it off the async executor. The
+    /// connection must share its connector rather than borrow it, as those from
+    /// `Connector::acquire_async` and `Connector::acquire_owned` do, and is handed back
+    /// alongside the results so it can be reused.
+    pub async fn run_async(
+        self,
+        cypher: String,
+        params: Value,
+    ) -> Result<(Self, Vec<Record>, Summary), BoltError> {
+        let res = tokio::task::spawn_blocking(move || {
+            let res = self.run_collect(&cypher, params);
+            res.map(|(rows, summary)| (self, rows, summary))
+        })
+        .await;
+        res.unwrap_or_else(|e| std::panic::resume_unwind(e.into_panic()))
+    }
+}
+
+/// The operations application code needs from a connection, so that it can be written
+/// against either a real `Connection` or, with the `mock` feature, a `MockConnection`.
+pub trait ConnectionLike {
+    /// Runs an auto-commit query (or one inside the open transaction) and collects every
+    /// record along with the summary.
+    fn run_collect(&self, cypher: &str, params: Value)
+        -> Result<(Vec<Record>, Summary), BoltError>;
+
+    fn begin(&self, metadata: Option<Value>) -> Result<(), BoltError>;
+
+    fn commit(&self) -> Result<(), BoltError>;
+
+    fn rollback(&self) -> Result<(), BoltError>;
+}
+
+impl<'a> ConnectionLike for Connection<'a> {
+    fn run_collect(
+        &self,
+        cypher: &str,
+        params: Value,
+    ) -> Result<(Vec<Record>, Summary), BoltError> {
+        let mut records = self.run(cypher, params)?;
+        let rows = records.by_ref().collect::<Result<Vec<_>, _>>()?;
+        Ok((rows, records.consume()?))
+    }
+
+    fn begin(&self, metadata: Option<Value>) -> Result<(), BoltError> {
+        Connection::begin(self, metadata)
+    }
+
+    fn commit(&self) -> Result<(), BoltError> {
+        Connection::commit(self)
+    }
+
+    fn rollback(&self) -> Result<(), BoltError> {
+        Connection::rollback(self)
+    }
+}
+
+impl<'a> Drop for Connection<'a> {
+    fn drop(&mut self) {
+        if !self.ptr.is_null() {
+            let _ = self.release();
+        }
+    }
+}
+
+// A seabolt connection has no thread affinity, it only must not be used from two threads
+// at once, so Connection is Send but deliberately not Sync.
+unsafe impl<'a> Send for Connection<'a> {}
+
+/// The rows of a query, streamed as they are iterated. The server sends every row whether
+/// or not they are read, as seabolt only speaks Bolt v1 to v3, which cannot pull a result in
+/// batches; they are still read off the socket one at a time, so memory use does not grow
+/// with the result. A query with no rows yields nothing:
+/// the PULL's summary is the first thing fetched, so iteration ends straight away, and
+/// `consume` then returns that summary as for any other result.
+#[derive(Debug)]
+pub struct Records<'c> {
+    conn: &'c Connection<'c>,
+    request: seabolt_sys::BoltRequest,
+    fields: Arc<[String]>,
+    run_metadata: Option<Value>,
+    done: bool,
+    summary: Option<Summary>,
+    failure: Option<BoltError>,
+    pipeline: Option<Rc<Cell<usize>>>,
+    trace: QueryTrace,
+    deadline: Option<Instant>,
+}
+
+impl<'c> Records<'c> {
+    fn new(
+        conn: &'c Connection<'c>,
+        request: seabolt_sys::BoltRequest,
+        fields: Arc<[String]>,
+        run_metadata: Value,
+        trace: QueryTrace,
+    ) -> Self {
+        Records {
+            conn,
+            request,
+            fields,
+            run_metadata: Some(run_metadata),
+            done: false,
+            summary: None,
+            failure: None,
+            pipeline: None,
+            trace,
+            deadline: None,
+        }
+    }
+
+    pub fn fields(&self) -> &[String] {
+        &self.fields
+    }
+
+    pub fn consume(mut self) -> Result<Summary, BoltError> {
+        while let Some(record) = self.next() {
+            record?;
+        }
+        match self.failure.take() {
+            Some(e) => Err(e),
+            None => Ok(self.summary.take().unwrap()),
+        }
+    }
+
+    fn finish(&mut self) {
+        self.done = true;
+        self.trace.finish(self.failure.as_ref());
+        if let Some(position) = self.pipeline.take() {
+            position.set(position.get() + 1);
+        }
+    }
+
+    fn fail(&mut self, e: BoltError) -> Option<Result<Record, BoltError>> {
+        self.failure = Some(e.clone());
+        self.finish();
+        Some(Err(e))
+    }
+}
+
+impl<'c> Iterator for Records<'c> {
+    type Item = Result<Record, BoltError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let ptr = self.conn.ptr;
+        if self.deadline.map_or(false, |d| Instant::now() >= d) {
+            let _ = self.conn.reset();
+            return self.fail(BoltError::Timeout);
+        }
+        let request = self.request;
+        match self
+            .trace
+            .in_scope(|| unsafe { seabolt_sys::BoltConnection_fetch(ptr, request) })
+        {
+            1 => match unsafe { Value::duplicate(seabolt_sys::BoltConnection_field_values(ptr)) } {
+                Some(values) => {
+                    self.trace.row();
+                    Some(Ok(Record::new(self.fields.clone(), values)))
+                }
+                None => {
+                    // Skip the rest of the stream so the connection can run another query.
+                    let _ = self.conn.fetch_summary(self.request);
+                    self.fail(BoltError::OutOfMemory)
+                }
+            },
+            0 if unsafe { seabolt_sys::BoltConnection_summary_success(ptr) } == 1 => {
+                match self.conn.metadata() {
+                    Ok(metadata) => {
+                        self.finish();
+                        let run_metadata = self.run_metadata.take().unwrap();
+                        self.summary = Some(Summary::new(run_metadata, metadata));
+                        None
+                    }
+                    Err(e) => self.fail(e),
+                }
+            }
+            _ => {
+                let e = self.conn.error();
+                self.fail(e)
+            }
+        }
+    }
+}
+
+impl<'c> Drop for Records<'c> {
+    fn drop(&mut self) {
+        if !self.done {
+            let _ = self.conn.fetch_summary(self.request);
+            self.finish();
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct QueryHandle<'c> {
+    conn: &'c Connection<'c>,
+    trace: Option<QueryTrace>,
+    run: seabolt_sys::BoltRequest,
+    pull: seabolt_sys::BoltRequest,
+    index: usize,
+    position: Rc<Cell<usize>>,
+    started: bool,
+}
+
+impl<'c> QueryHandle<'c> {
+    pub fn records(mut self) -> Result<Records<'c>, BoltError> {
+        let expected = self.position.get();
+        if expected != self.index {
+            return Err(BoltError::PipelineOrder {
+                expected,
+                found: self.index,
+            });
+        }
+        self.started = true;
+        let trace = self.trace.take().unwrap();
+        match self.conn.start_records(trace, self.run, self.pull) {
+            Ok(mut records) => {
+                records.pipeline = Some(self.position.clone());
+                Ok(records)
+            }
+            Err(e) => {
+                self.position.set(expected + 1);
+                Err(e)
+            }
+        }
+    }
+}
+
+impl<'c> Drop for QueryHandle<'c> {
+    fn drop(&mut self) {
+        if !self.started && self.position.get() == self.index {
+            let _ = self.conn.fetch_summary(self.run);
+            let _ = self.conn.fetch_summary(self.pull);
+            self.position.set(self.index + 1);
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Record {
+    fields: Arc<[String]>,
+    values: Value,
+}
+
+impl Record {
+    pub(crate) fn new(fields: Arc<[String]>, values: Value) -> Self {
+        Record { fields, values }
+    }
+
+    pub fn fields(&self) -> &[String] {
+        &self.fields
+    }
+
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    pub fn get(&self, key: &str) -> Option<ValueRef<'_>> {
+        let i = self.fields.iter().position(|f| f == key)?;
+        self.values.list_get(i)
+    }
+
+    pub fn get_index(&self, i: usize) -> Option<ValueRef<'_>> {
+        self.values.list_get(i)
+    }
+
+    /// Deep-copies every field out of the row, unlike `get` which borrows in place.
+    pub fn into_values(self) -> Vec<Value> {
+        (0..self.len())
+            .filter_map(|i| self.values.list_get(i))
+            .map(|v| v.clone())
+            .collect()
+    }
+
+    /// Deep-copies every field keyed by its name, unlike `get` which borrows in place.
+    pub fn into_hashmap(self) -> HashMap<String, Value> {
+        self.fields
+            .iter()
+            .enumerate()
+            .filter_map(|(i, f)| self.values.list_get(i).map(|v| (f.clone(), v.clone())))
+            .collect()
+    }
+}
@@ -0,0 +1,119 @@
+use std::fmt;
+
+use crate::{Value, ValueRef, ValueType};
+
+/// One place where two values differ, as found by `Value::diff`. `path` leads there from
+/// the top-level value through dictionary keys and list or structure indices, e.g.
+/// `.people[2].name`, and is empty for the top-level value itself. `expected` is `None` for
+/// a key or element only `actual` has, and `actual` is `None` for one it is missing.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ValueDiff {
+    pub path: String,
+    pub expected: Option<Value>,
+    pub actual: Option<Value>,
+}
+
+impl fmt::Display for ValueDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let path = if self.path.is_empty() { "." } else { &self.path };
+        match (&self.expected, &self.actual) {
+            (Some(expected), Some(actual)) => write!(
+                f,
+                "{}: expected {}, found {}",
+                path,
+                describe(expected),
+                describe(actual)
+            ),
+            (Some(expected), None) => write!(f, "{}: missing {}", path, describe(expected)),
+            (None, Some(actual)) => write!(f, "{}: unexpected {}", path, describe(actual)),
+            (None, None) => write!(f, "{}: no difference", path),
+        }
+    }
+}
+
+impl Value {
+    /// Lists every difference between `self`, taken as the expected value, and `other`,
+    /// empty exactly when the two are equal. Dictionaries are compared key by key and lists
+    /// and structures element by element, so a failing test assertion can point at the
+    /// offending field rather than print both values whole.
+    pub fn diff(&self, other: &Value) -> Vec<ValueDiff> {
+        let mut diffs = Vec::new();
+        diff_into(&mut diffs, String::new(), self, other);
+        diffs
+    }
+}
+
+fn diff_into(diffs: &mut Vec<ValueDiff>, path: String, expected: &Value, actual: &Value) {
+    if expected == actual {
+        return;
+    }
+    match (expected.get_type(), actual.get_type()) {
+        (ValueType::Dictionary, ValueType::Dictionary) => {
+            for (k, e) in expected.iter_dict() {
+                let path = format!("{}.{}", path, k);
+                match actual.get(k) {
+                    Some(a) => diff_into(diffs, path, &e, &a),
+                    None => diffs.push(ValueDiff {
+                        path,
+                        expected: Some(e.clone()),
+                        actual: None,
+                    }),
+                }
+            }
+            for (k, a) in actual.iter_dict().filter(|(k, _)| !expected.contains_key(k)) {
+                diffs.push(ValueDiff {
+                    path: format!("{}.{}", path, k),
+                    expected: None,
+                    actual: Some(a.clone()),
+                });
+            }
+        }
+        (ValueType::List, ValueType::List) => {
+            diff_elements(diffs, &path, expected.len(), actual.len(), |i| {
+                (expected.list_get(i), actual.list_get(i))
+            });
+        }
+        (ValueType::Structure, ValueType::Structure)
+            if expected.structure_code() == actual.structure_code() =>
+        {
+            diff_elements(diffs, &path, expected.len(), actual.len(), |i| {
+                (expected.structure_field(i), actual.structure_field(i))
+            });
+        }
+        _ => diffs.push(ValueDiff {
+            path,
+            expected: Some(expected.clone()),
+            actual: Some(actual.clone()),
+        }),
+    }
+}
+
+fn diff_elements<'a, F>(diffs: &mut Vec<ValueDiff>, path: &str, n: usize, m: usize, get: F)
+where
+    F: Fn(usize) -> (Option<ValueRef<'a>>, Option<ValueRef<'a>>),
+{
+    for i in 0..n.max(m) {
+        let path = format!("{}[{}]", path, i);
+        match get(i) {
+            (Some(e), Some(a)) => diff_into(diffs, path, &e, &a),
+            (e, a) => diffs.push(ValueDiff {
+                path,
+                expected: e.map(|v| v.clone()),
+                actual: a.map(|v| v.clone()),
+            }),
+        }
+    }
+}
+
+/// Renders scalars in full but only the type and size of containers, so that one line
+/// stays readable.
+pub(crate) fn describe(v: &Value) -> String {
+    match v.get_type() {
+        ValueType::Null => "null".to_string(),
+        ValueType::Boolean => v.as_boolean().to_string(),
+        ValueType::Integer => v.as_integer().to_string(),
+        ValueType::Float => v.as_float().to_string(),
+        ValueType::String => format!("{:?}", v.as_str_lossy()),
+        t => format!("<{:?} of {}>", t, v.len()),
+    }
+}
@@ -0,0 +1,125 @@
+use std::sync::Arc;
+
+use crate::{
+    basic_auth,
+    config::{ConfigBuilder, Scheme, Transport},
+    AccessMode, Address, Addresses, Auth, Bolt, BoltError, Config, Connector, Session,
+};
+
+/// Initializes seabolt and holds a connector for one database, the counterpart of the
+/// official drivers' `GraphDatabase.driver(uri, auth)`. Its connector keeps seabolt running,
+/// so only one `Driver` (or `Bolt`) can exist at a time. Clones share the connector, and
+/// the pool is closed and seabolt shut down again once the last clone, and any connection
+/// from `Connector::acquire_owned`, has dropped.
+#[derive(Debug, Clone)]
+pub struct Driver {
+    connector: Arc<Connector>,
+}
+
+impl Driver {
+    /// `uri` is `bolt://host[:port]` for a single server or `neo4j://host[:port][,...]` for
+    /// a routed cluster, with a `+s` suffix on the scheme (`bolt+s`, `neo4j+s`) for an
+    /// encrypted transport. The port defaults to 7687.
+    pub fn builder(uri: &str) -> DriverBuilder {
+        DriverBuilder {
+            uri: uri.to_string(),
+            auth: None,
+            config: Config::build(),
+        }
+    }
+
+    /// Shared, so that `Connector::acquire_owned` and `acquire_async` can hand out
+    /// connections that outlive this borrow.
+    pub fn connector(&self) -> &Arc<Connector> {
+        &self.connector
+    }
+
+    /// Opens a session on the default database, see `Connector::session`.
+    pub fn session(&self, mode: AccessMode) -> Result<Session<'_>, BoltError> {
+        self.connector.session(None, mode)
+    }
+}
+
+#[derive(Debug)]
+pub struct DriverBuilder {
+    uri: String,
+    auth: Option<Auth>,
+    config: ConfigBuilder,
+}
+
+impl DriverBuilder {
+    pub fn with_basic_auth(self, username: &str, password: &str) -> Self {
+        self.with_auth(basic_auth(username, password, None))
+    }
+
+    pub fn with_auth(mut self, auth: Auth) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
+    /// Adjusts the connector's configuration. The scheme and transport are taken from the
+    /// URI and override anything set here.
+    pub fn with_config<F: FnOnce(ConfigBuilder) -> ConfigBuilder>(mut self, f: F) -> Self {
+        self.config = f(self.config);
+        self
+    }
+
+    /// Fails with `BoltError::InvalidConfig` for a malformed URI or configuration, with
+    /// `BoltError::AlreadyInitialized` if seabolt is already in use, and, since the pool
+    /// connects lazily, never because the server is unreachable; see `Connector::warm_up`.
+    pub fn build(self) -> Result<Driver, BoltError> {
+        let invalid = |reason: String| BoltError::InvalidConfig { reason };
+        let (scheme, hosts) = self
+            .uri
+            .split_once("://")
+            .ok_or_else(|| invalid(format!("{:?} is not a URI", self.uri)))?;
+        let (routing, encrypted) = match scheme {
+            "bolt" => (false, false),
+            "bolt+s" => (false, true),
+            "neo4j" => (true, false),
+            "neo4j+s" => (true, true),
+            _ => return Err(invalid(format!("unsupported URI scheme {:?}", scheme))),
+        };
+        let mut addrs = hosts
+            .trim_end_matches('/')
+            .split(',')
+            .map(str::trim)
+            .map(Address::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+        if addrs.iter().any(|a| a.get_host().is_empty()) {
+            return Err(invalid(format!("{:?} is missing a host", self.uri)));
+        }
+        if !routing && addrs.len() != 1 {
+            return Err(invalid("a bolt:// URI takes exactly one host".to_string()));
+        }
+
+        let transport = if encrypted {
+            Transport::Encrypted
+        } else {
+            Transport::Plaintext
+        };
+        let scheme = if routing {
+            Scheme::Neo4j
+        } else {
+            Scheme::Direct
+        };
+        let config = self
+            .config
+            .with_scheme(scheme)
+            .with_transport(transport)
+            .try_finish()?;
+        let auth = self
+            .auth
+            .ok_or_else(|| invalid("no credentials were given".to_string()))?;
+
+        let bolt = Bolt::init()?;
+        let connector = if routing {
+            bolt.create_routing_connector(Addresses::new(addrs), &auth, &config)
+        } else {
+            bolt.create_connector(&addrs.remove(0), &auth, &config)
+        };
+        Ok(Driver {
+            connector: Arc::new(connector),
+        })
+    }
+}
@@ -0,0 +1,250 @@
+use std::{error, ffi::CStr, fmt, io};
+
+use crate::{InputPosition, StructureKind, Value, ValueType};
+
+/// Neo4j's classification of a server error, from the second part of its dotted code,
+/// plus the failures that never reached a server.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum ErrorCategory {
+    /// `Neo.ClientError.*`, other than security errors.
+    Client,
+    /// `Neo.ClientError.Security.*`.
+    Security,
+    /// `Neo.TransientError.*`.
+    Transient,
+    /// `Neo.DatabaseError.*`, or a server code that does not follow the scheme.
+    Database,
+    /// seabolt could not reach, or lost, the server.
+    Connection,
+    /// Misuse caught by this crate before anything was sent.
+    Driver,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BoltError {
+    AlreadyInitialized,
+    Connection {
+        code: i32,
+        context: Option<String>,
+    },
+    Server {
+        code: String,
+        message: String,
+        /// Where in the query the error is, for syntax and semantic errors.
+        position: Option<InputPosition>,
+    },
+    TypeMismatch {
+        expected: ValueType,
+        found: ValueType,
+    },
+    IntegerOutOfRange {
+        value: i128,
+        target: &'static str,
+    },
+    UnexpectedStructure {
+        expected: StructureKind,
+        found: StructureKind,
+    },
+    StructureArity {
+        kind: StructureKind,
+        expected: usize,
+        found: usize,
+    },
+    PipelineOrder {
+        expected: usize,
+        found: usize,
+    },
+    NoRecords,
+    MultipleRecords,
+    UnknownVariant {
+        name: &'static str,
+    },
+    InvalidConfig {
+        reason: String,
+    },
+    IndexOutOfBounds {
+        index: usize,
+        len: usize,
+    },
+    Serialization {
+        message: String,
+    },
+    MissingColumn {
+        name: String,
+    },
+    Timeout,
+    OutOfMemory,
+    Draining,
+    UnboundParameter {
+        name: String,
+    },
+}
+
+impl BoltError {
+    pub(crate) unsafe fn from_status(status: *mut seabolt_sys::BoltStatus) -> Self {
+        let code = seabolt_sys::BoltStatus_get_error(status);
+        let context = seabolt_sys::BoltStatus_get_error_context(status);
+        let context = if context.is_null() {
+            None
+        } else {
+            Some(CStr::from_ptr(context).to_string_lossy().into_owned())
+        };
+        BoltError::Connection { code, context }
+    }
+
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            BoltError::Server { code, .. } => {
+                let mut parts = code.split('.').skip(1);
+                match (parts.next(), parts.next()) {
+                    (Some("ClientError"), Some("Security")) => ErrorCategory::Security,
+                    (Some("ClientError"), _) => ErrorCategory::Client,
+                    (Some("TransientError"), _) => ErrorCategory::Transient,
+                    _ => ErrorCategory::Database,
+                }
+            }
+            BoltError::Connection { .. } => ErrorCategory::Connection,
+            _ => ErrorCategory::Driver,
+        }
+    }
+
+    /// Whether the same work may succeed if tried again: transient server errors, a cluster
+    /// member that is no longer the leader, and dropped or timed out connections.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            BoltError::Server { code, .. } => match self.category() {
+                // Terminated by the user, so retrying would undo their intent.
+                ErrorCategory::Transient => {
+                    code != "Neo.TransientError.Transaction.Terminated"
+                        && code != "Neo.TransientError.Transaction.LockClientStopped"
+                }
+                ErrorCategory::Client => {
+                    code == "Neo.ClientError.Cluster.NotALeader"
+                        || code == "Neo.ClientError.General.ForbiddenOnReadOnlyDatabase"
+                }
+                _ => false,
+            },
+            BoltError::Connection { code, .. } => matches!(
+                *code as u32,
+                seabolt_sys::BOLT_CONNECTION_RESET
+                    | seabolt_sys::BOLT_END_OF_TRANSMISSION
+                    | seabolt_sys::BOLT_TIMED_OUT
+                    | seabolt_sys::BOLT_INTERRUPTED
+                    | seabolt_sys::BOLT_POOL_ACQUISITION_TIMED_OUT
+                    | seabolt_sys::BOLT_ROUTING_NO_SERVERS_TO_SELECT
+                    | seabolt_sys::BOLT_ROUTING_UNABLE_TO_REFRESH_ROUTING_TABLE
+            ),
+            _ => false,
+        }
+    }
+
+    pub(crate) fn from_failure(failure: &Value) -> Self {
+        let field = |key| {
+            failure
+                .get(key)
+                .filter(|v| v.get_type() == ValueType::String)
+                .map(|v| v.as_string().to_string())
+                .unwrap_or_default()
+        };
+        let message = field("message");
+        let position = match failure.get("position") {
+            Some(p) if p.get_type() == ValueType::Dictionary => {
+                Some(InputPosition::from_value(&p))
+            }
+            _ => InputPosition::from_message(&message),
+        };
+        BoltError::Server {
+            code: field("code"),
+            message,
+            position,
+        }
+    }
+}
+
+impl fmt::Display for BoltError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BoltError::AlreadyInitialized => write!(f, "seabolt is already initialized"),
+            BoltError::Connection { code, context } => {
+                let s = unsafe { CStr::from_ptr(seabolt_sys::BoltError_get_string(*code)) };
+                write!(f, "{}", s.to_string_lossy())?;
+                if let Some(context) = context {
+                    write!(f, " ({})", context)?;
+                }
+                Ok(())
+            }
+            BoltError::Server { code, message, .. } => write!(f, "{}: {}", code, message),
+            BoltError::TypeMismatch { expected, found } => {
+                write!(f, "expected a {:?} value, found {:?}", expected, found)
+            }
+            BoltError::IntegerOutOfRange { value, target } => {
+                write!(f, "integer {} does not fit in {}", value, target)
+            }
+            BoltError::UnexpectedStructure { expected, found } => {
+                write!(f, "expected a {:?} structure, found {:?}", expected, found)
+            }
+            BoltError::StructureArity {
+                kind,
+                expected,
+                found,
+            } => write!(
+                f,
+                "expected {} fields in a {:?} structure, found {}",
+                expected, kind, found
+            ),
+            BoltError::PipelineOrder { expected, found } => write!(
+                f,
+                "pipelined query {} was started before query {} finished",
+                found, expected
+            ),
+            BoltError::NoRecords => write!(f, "expected a single record, found none"),
+            BoltError::MultipleRecords => {
+                write!(f, "expected a single record, found more than one")
+            }
+            BoltError::UnknownVariant { name } => {
+                write!(f, "{}::Unknown cannot be passed to seabolt", name)
+            }
+            BoltError::InvalidConfig { reason } => write!(f, "invalid configuration: {}", reason),
+            BoltError::IndexOutOfBounds { index, len } => {
+                write!(f, "index {} is out of bounds for a list of {}", index, len)
+            }
+            BoltError::Serialization { message } => write!(f, "serialization failed: {}", message),
+            BoltError::MissingColumn { name } => write!(f, "the result has no column {:?}", name),
+            BoltError::Timeout => write!(f, "the query did not finish before its deadline"),
+            BoltError::OutOfMemory => write!(f, "seabolt failed to allocate a value"),
+            BoltError::Draining => write!(f, "the connector is draining its pool"),
+            BoltError::UnboundParameter { name } => {
+                write!(f, "the query uses ${} but no value was given for it", name)
+            }
+        }
+    }
+}
+
+impl error::Error for BoltError {}
+
+impl From<BoltError> for io::Error {
+    fn from(e: BoltError) -> Self {
+        let kind = match &e {
+            BoltError::Connection { code, .. } => match *code as u32 {
+                seabolt_sys::BOLT_TIMED_OUT | seabolt_sys::BOLT_POOL_ACQUISITION_TIMED_OUT => {
+                    io::ErrorKind::TimedOut
+                }
+                seabolt_sys::BOLT_CONNECTION_REFUSED => io::ErrorKind::ConnectionRefused,
+                seabolt_sys::BOLT_CONNECTION_RESET => io::ErrorKind::ConnectionReset,
+                seabolt_sys::BOLT_END_OF_TRANSMISSION => io::ErrorKind::UnexpectedEof,
+                seabolt_sys::BOLT_INTERRUPTED => io::ErrorKind::Interrupted,
+                seabolt_sys::BOLT_PERMISSION_DENIED => io::ErrorKind::PermissionDenied,
+                seabolt_sys::BOLT_NO_VALID_ADDRESS | seabolt_sys::BOLT_ADDRESS_NOT_RESOLVED => {
+                    io::ErrorKind::AddrNotAvailable
+                }
+                _ => io::ErrorKind::Other,
+            },
+            BoltError::TypeMismatch { .. }
+            | BoltError::IntegerOutOfRange { .. }
+            | BoltError::UnexpectedStructure { .. }
+            | BoltError::StructureArity { .. } => io::ErrorKind::InvalidData,
+            _ => io::ErrorKind::Other,
+        };
+        io::Error::new(kind, e)
+    }
+}
@@ -0,0 +1,114 @@
+/// Hashes a query's text into a key for grouping telemetry by statement, whatever its
+/// parameters. Runs of whitespace outside quoted strings and identifiers count as a single
+/// space and leading and trailing whitespace is ignored; case is kept, since labels,
+/// property names and literals are case-sensitive. The hash is FNV-1a, so fingerprints are
+/// stable across processes and Rust versions.
+pub fn fingerprint(cypher: &str) -> u64 {
+    let mut hash = Fnv::new();
+    let mut quote = None;
+    let mut escaped = false;
+    let mut pending_space = false;
+    for c in cypher.trim().chars() {
+        match quote {
+            Some(q) => {
+                if escaped {
+                    escaped = false;
+                } else if c == '\\' {
+                    escaped = true;
+                } else if c == q {
+                    quote = None;
+                }
+            }
+            None if c.is_whitespace() => {
+                pending_space = true;
+                continue;
+            }
+            None => {
+                if c == '\'' || c == '"' || c == '`' {
+                    quote = Some(c);
+                }
+            }
+        }
+        if pending_space {
+            hash.write_char(' ');
+            pending_space = false;
+        }
+        hash.write_char(c);
+    }
+    hash.0
+}
+
+struct Fnv(u64);
+
+impl Fnv {
+    fn new() -> Self {
+        Fnv(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write_char(&mut self, c: char) {
+        let mut buf = [0; 4];
+        for b in c.encode_utf8(&mut buf).bytes() {
+            self.0 ^= u64::from(b);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collapses_whitespace() {
+        assert_eq!(
+            fingerprint("MATCH (n)\n\tRETURN   n"),
+            fingerprint("MATCH (n) RETURN n")
+        );
+        assert_ne!(
+            fingerprint("MATCH (n) RETURN n"),
+            fingerprint("MATCH (n)RETURN n")
+        );
+    }
+
+    #[test]
+    fn trims_ends() {
+        assert_eq!(fingerprint("  RETURN 1\n"), fingerprint("RETURN 1"));
+    }
+
+    #[test]
+    fn keeps_whitespace_in_quotes() {
+        assert_ne!(fingerprint("RETURN 'a  b'"), fingerprint("RETURN 'a b'"));
+        assert_ne!(fingerprint("RETURN `a  b`"), fingerprint("RETURN `a b`"));
+        assert_ne!(
+            fingerprint(r"RETURN 'it\'s  x'"),
+            fingerprint(r"RETURN 'it\'s x'")
+        );
+    }
+
+    #[test]
+    fn case_sensitive() {
+        assert_ne!(
+            fingerprint("MATCH (n:Person)"),
+            fingerprint("MATCH (n:person)")
+        );
+        assert_ne!(fingerprint("match (n)"), fingerprint("MATCH (n)"));
+    }
+
+    #[test]
+    fn ignores_parameter_values() {
+        // Only the text is hashed, so a parameterised query has one fingerprint whatever
+        // it is run with, whereas inlined literals make each value a separate statement.
+        let cypher = "MATCH (p:Person {name: $name}) RETURN p";
+        assert_eq!(fingerprint(cypher), fingerprint(cypher));
+        assert_ne!(
+            fingerprint("MATCH (p:Person {name: 'Alice'}) RETURN p"),
+            fingerprint("MATCH (p:Person {name: 'Bob'}) RETURN p")
+        );
+    }
+
+    #[test]
+    fn stable() {
+        assert_eq!(fingerprint(""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fingerprint("a"), 0xaf63_dc4c_8601_ec8c);
+    }
+}
@@ -0,0 +1,104 @@
+use std::{collections::HashMap, convert::TryFrom};
+
+use crate::{BoltError, StructureKind, Value, ValueType};
+
+// Bolt 5 appends string element ids to each graph structure and deprecates the integer
+// ids. seabolt does not report the negotiated version, so the field count decides which
+// layout a structure has; the `element_id` fields are `None` for the older one.
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Node {
+    pub id: i64,
+    pub labels: Vec<String>,
+    pub properties: HashMap<String, Value>,
+    pub element_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Relationship {
+    pub id: i64,
+    pub start_node_id: i64,
+    pub end_node_id: i64,
+    pub rel_type: String,
+    pub properties: HashMap<String, Value>,
+    pub element_id: Option<String>,
+    pub start_node_element_id: Option<String>,
+    pub end_node_element_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct UnboundRelationship {
+    pub id: i64,
+    pub rel_type: String,
+    pub properties: HashMap<String, Value>,
+    pub element_id: Option<String>,
+}
+
+impl Value {
+    pub fn as_node(&self) -> Result<Node, BoltError> {
+        let bolt5 = self.expect_graph_structure(StructureKind::Node, 3, 1)?;
+        let labels = self.structure_field(1).unwrap();
+        labels.expect_type(ValueType::List)?;
+        Ok(Node {
+            id: i64::try_from(&*self.structure_field(0).unwrap())?,
+            labels: (0..labels.len())
+                .map(|i| String::try_from(&*labels.list_get(i).unwrap()))
+                .collect::<Result<_, _>>()?,
+            properties: properties(&self.structure_field(2).unwrap())?,
+            element_id: self.element_id(bolt5, 3)?,
+        })
+    }
+
+    pub fn as_relationship(&self) -> Result<Relationship, BoltError> {
+        let bolt5 = self.expect_graph_structure(StructureKind::Relationship, 5, 3)?;
+        Ok(Relationship {
+            id: i64::try_from(&*self.structure_field(0).unwrap())?,
+            start_node_id: i64::try_from(&*self.structure_field(1).unwrap())?,
+            end_node_id: i64::try_from(&*self.structure_field(2).unwrap())?,
+            rel_type: String::try_from(&*self.structure_field(3).unwrap())?,
+            properties: properties(&self.structure_field(4).unwrap())?,
+            element_id: self.element_id(bolt5, 5)?,
+            start_node_element_id: self.element_id(bolt5, 6)?,
+            end_node_element_id: self.element_id(bolt5, 7)?,
+        })
+    }
+
+    pub fn as_unbound_relationship(&self) -> Result<UnboundRelationship, BoltError> {
+        let bolt5 = self.expect_graph_structure(StructureKind::UnboundRelationship, 3, 1)?;
+        Ok(UnboundRelationship {
+            id: i64::try_from(&*self.structure_field(0).unwrap())?,
+            rel_type: String::try_from(&*self.structure_field(1).unwrap())?,
+            properties: properties(&self.structure_field(2).unwrap())?,
+            element_id: self.element_id(bolt5, 3)?,
+        })
+    }
+
+    // Accepts either the original layout of `arity` fields or the Bolt 5 one with `extra`
+    // element ids on the end, returning whether it is the latter.
+    fn expect_graph_structure(
+        &self,
+        kind: StructureKind,
+        arity: usize,
+        extra: usize,
+    ) -> Result<bool, BoltError> {
+        match self.expect_structure(kind, arity) {
+            Ok(()) => Ok(false),
+            Err(BoltError::StructureArity { .. }) if self.len() == arity + extra => Ok(true),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn element_id(&self, bolt5: bool, i: usize) -> Result<Option<String>, BoltError> {
+        if !bolt5 {
+            return Ok(None);
+        }
+        String::try_from(&*self.structure_field(i).unwrap()).map(Some)
+    }
+}
+
+fn properties(v: &Value) -> Result<HashMap<String, Value>, BoltError> {
+    v.expect_type(ValueType::Dictionary)?;
+    Ok(v.iter_dict()
+        .map(|(k, v)| (k.to_string(), v.clone()))
+        .collect())
+}
@@ -0,0 +1,70 @@
+use serde::{
+    ser::{SerializeMap, SerializeSeq},
+    Serialize, Serializer,
+};
+
+use crate::{BoltError, Value, ValueType};
+
+// Structures have no natural JSON form, so they become `{"code": .., "fields": [..]}`.
+impl Serialize for Value {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.get_type() {
+            ValueType::Null | ValueType::Unknown => serializer.serialize_unit(),
+            ValueType::Boolean => serializer.serialize_bool(self.as_boolean()),
+            ValueType::Integer => serializer.serialize_i64(self.as_integer()),
+            ValueType::Float => serializer.serialize_f64(self.as_float()),
+            ValueType::String => serializer.serialize_str(&self.as_str_lossy()),
+            ValueType::Bytes => serializer.serialize_bytes(self.as_bytes()),
+            ValueType::List => {
+                let mut seq = serializer.serialize_seq(Some(self.len()))?;
+                for i in 0..self.len() {
+                    seq.serialize_element(&*self.list_get(i).unwrap())?;
+                }
+                seq.end()
+            }
+            ValueType::Dictionary => {
+                let mut map = serializer.serialize_map(Some(self.len()))?;
+                for (k, v) in self.iter_dict() {
+                    map.serialize_entry(k, &*v)?;
+                }
+                map.end()
+            }
+            ValueType::Structure => {
+                let mut map = serializer.serialize_map(Some(2))?;
+                map.serialize_entry("code", &self.structure_code())?;
+                map.serialize_entry("fields", &Fields(self))?;
+                map.end()
+            }
+        }
+    }
+}
+
+struct Fields<'a>(&'a Value);
+
+impl<'a> Serialize for Fields<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.0.len()))?;
+        for i in 0..self.0.len() {
+            seq.serialize_element(&*self.0.structure_field(i).unwrap())?;
+        }
+        seq.end()
+    }
+}
+
+impl Value {
+    /// Serializes this value to compact JSON.
+    pub fn to_json_string(&self) -> Result<String, BoltError> {
+        serde_json::to_string(self).map_err(serialization_error)
+    }
+
+    /// Serializes this value to indented JSON.
+    pub fn to_json_pretty(&self) -> Result<String, BoltError> {
+        serde_json::to_string_pretty(self).map_err(serialization_error)
+    }
+}
+
+fn serialization_error(e: serde_json::Error) -> BoltError {
+    BoltError::Serialization {
+        message: e.to_string(),
+    }
+}
@@ -1,8 +1,15 @@
 use std::{
+    borrow::Cow,
+    collections::HashMap,
     ffi::{CStr, CString},
-    marker::PhantomData,
-    ptr,
-    sync::Mutex,
+    fmt, ptr,
+    str::Utf8Error,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
 };
 
 use lazy_static::lazy_static;
@@ -11,24 +18,28 @@ use seabolt_sys;
 macro_rules! make_enum {
     ($name:ident,
      $($variant:ident => $constant:path),+ $(,)?) => {
-        #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+        #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
         pub enum $name {
             $($variant,)+
             Unknown,
         }
 
         impl $name {
-            fn from_idx(t: u32) -> Self {
+            pub(crate) fn from_idx(t: u32) -> Self {
                 match t {
                     $($constant => $name::$variant,)+
                     _ => $name::Unknown,
                 }
             }
 
-            fn as_idx(&self) -> u32 {
+            // `Unknown` only exists for decoding values seabolt hands back, so it has no
+            // encoding of its own.
+            pub(crate) fn as_idx(&self) -> Result<u32, crate::BoltError> {
                 match self {
-                    $($name::$variant => $constant,)+
-                    $name::Unknown => unimplemented!(),
+                    $($name::$variant => Ok($constant),)+
+                    $name::Unknown => Err(crate::BoltError::UnknownVariant {
+                        name: stringify!($name),
+                    }),
                 }
             }
         }
@@ -36,67 +47,289 @@ macro_rules! make_enum {
 }
 
 pub mod config;
+mod connection;
+mod diff;
+mod driver;
+mod error;
+mod fingerprint;
+mod graph;
+#[cfg(feature = "serde")]
+mod json;
+#[cfg(feature = "log")]
+mod logging;
+#[cfg(feature = "mock")]
+mod mock;
+mod params;
+mod pool;
+pub mod prelude;
+mod record;
+mod retry;
+mod routing;
+mod session;
+mod summary;
+mod temporal;
+mod trace;
+mod transaction;
 mod value;
+mod visit;
 pub use config::Config;
-pub use value::{Value, ValueType};
+pub use connection::{AccessMode, Connection, ConnectionLike, QueryHandle, Record, Records};
+pub use diff::ValueDiff;
+pub use driver::{Driver, DriverBuilder};
+pub use error::{BoltError, ErrorCategory};
+pub use fingerprint::fingerprint;
+pub use graph::{Node, Relationship, UnboundRelationship};
+#[cfg(feature = "mock")]
+pub use mock::{MockConnection, MockConnectionBuilder};
+pub use params::{Params, Query, RunMetadata};
+pub use pool::ValuePool;
+pub use record::{FromColumn, FromRecord};
+pub use retry::{retry, RetryPolicy};
+pub use routing::{Addresses, RoutingTable};
+#[cfg(feature = "derive")]
+pub use seabolt_derive::FromRecord;
+pub use session::{ResultCursor, Session};
+pub use summary::{
+    Bookmark, InputPosition, Notification, Plan, QueryType, Summary, SummaryCounters,
+};
+pub use temporal::{LocalTime, Time};
+#[cfg(feature = "tracing")]
+pub use trace::TraceOptions;
+pub use transaction::Tx;
+pub use value::{SharedValue, Structure, StructureKind, Value, ValueRef, ValueType};
+pub use visit::ValueVisitor;
+
+lazy_static! {
+    static ref ACTIVE: Mutex<bool> = Mutex::new(false);
+}
 
+// Shuts seabolt down once the `Bolt` and every `Connector` created from it have dropped.
 #[derive(Debug)]
-pub struct Bolt;
+struct Runtime;
+
+impl Drop for Runtime {
+    fn drop(&mut self) {
+        let mut active = ACTIVE.lock().unwrap();
+        unsafe {
+            seabolt_sys::Bolt_shutdown();
+        }
+        *active = false;
+    }
+}
+
+/// seabolt must be torn down from the inside out: every connection released
+/// (`BoltConnector_release`) before its connector is destroyed (`BoltConnector_destroy`),
+/// and every connector destroyed before `Bolt_shutdown`. Each `Connection` borrows or
+/// shares its `Connector`, and each `Connector` keeps seabolt running until it is
+/// destroyed, so `Bolt_shutdown` only happens once the `Bolt` and all of its connectors
+/// have dropped, whichever goes last. Until then `init` fails with
+/// `BoltError::AlreadyInitialized`.
+#[derive(Debug)]
+pub struct Bolt {
+    runtime: Arc<Runtime>,
+    #[cfg(feature = "log")]
+    logger: Option<logging::Logger>,
+    #[cfg(feature = "tracing")]
+    trace_options: TraceOptions,
+}
 
 impl Bolt {
-    pub fn init() -> Option<Self> {
-        lazy_static! {
-            static ref SINGLE: Mutex<Option<Bolt>> = Mutex::new(Some(Bolt));
-        };
-        let init = SINGLE.lock().unwrap().take();
-        if init.is_some() {
-            unsafe {
-                seabolt_sys::Bolt_startup();
-            }
+    pub fn init() -> Result<Self, BoltError> {
+        let mut active = ACTIVE.lock().unwrap();
+        if *active {
+            return Err(BoltError::AlreadyInitialized);
+        }
+        unsafe {
+            seabolt_sys::Bolt_startup();
         }
-        init
+        *active = true;
+        Ok(Bolt {
+            runtime: Arc::new(Runtime),
+            #[cfg(feature = "log")]
+            logger: None,
+            #[cfg(feature = "tracing")]
+            trace_options: TraceOptions::default(),
+        })
+    }
+
+    /// Routes seabolt's own log output into the `log` facade under the `seabolt` target.
+    /// Only connectors created after this call pick the logger up.
+    #[cfg(feature = "log")]
+    pub fn set_logger(&mut self) {
+        self.logger = Some(logging::Logger::new());
+    }
+
+    /// Sets what the `bolt.query` span opened around each query records. Only connectors
+    /// created after this call pick the options up.
+    #[cfg(feature = "tracing")]
+    pub fn set_tracing(&mut self, options: TraceOptions) {
+        self.trace_options = options;
     }
 
     pub fn create_connector(&self, addr: &Address, auth: &Auth, config: &Config) -> Connector {
-        Connector::new(addr, auth, config)
+        let connector = self.install_logger(config, || {
+            Connector::new(self.runtime.clone(), addr, auth, config)
+        });
+        self.traced(connector)
     }
-}
 
-impl Drop for Bolt {
-    fn drop(&mut self) {
-        unsafe {
-            seabolt_sys::Bolt_shutdown();
+    /// Creates a connector that falls back through several seed routers, for `neo4j://`
+    /// clusters.
+    pub fn create_routing_connector(
+        &self,
+        seeds: Addresses,
+        auth: &Auth,
+        config: &Config,
+    ) -> Connector {
+        let resolver = routing::Resolver::new(seeds);
+        let mut connector = self.install_logger(config, || {
+            resolver.install(config.as_ptr(), || {
+                Connector::new(
+                    self.runtime.clone(),
+                    resolver.seeds().primary(),
+                    auth,
+                    config,
+                )
+            })
+        });
+        connector.resolver = Some(resolver);
+        self.traced(connector)
+    }
+
+    #[allow(unused_mut)]
+    fn traced(&self, mut connector: Connector) -> Connector {
+        #[cfg(feature = "tracing")]
+        {
+            connector.trace_options = self.trace_options;
+        }
+        connector
+    }
+
+    // Installs the logger on `config` only for the duration of `f`, as the config may
+    // outlive this Bolt and with it the logger.
+    fn install_logger<T>(&self, _config: &Config, f: impl FnOnce() -> T) -> T {
+        #[cfg(feature = "log")]
+        {
+            if let Some(logger) = &self.logger {
+                let config = _config.as_ptr();
+                unsafe { seabolt_sys::BoltConfig_set_log(config, logger.as_ptr()) };
+                let result = f();
+                unsafe { seabolt_sys::BoltConfig_set_log(config, ptr::null_mut()) };
+                return result;
+            }
         }
+        f()
     }
 }
 
+#[repr(transparent)]
 #[derive(Debug)]
 pub struct Address {
     ptr: *mut seabolt_sys::BoltAddress,
 }
 
 impl Address {
+    /// Panics where `try_new` would return an error.
     pub fn new(addr: &str, port: &str) -> Self {
-        let addr = CString::new(addr).unwrap();
-        let port = CString::new(port).unwrap();
+        Address::try_new(addr, port).unwrap_or_else(|e| panic!("invalid address: {}", e))
+    }
+
+    /// Fails with `BoltError::InvalidConfig` if either part contains a NUL byte, and with
+    /// `BoltError::OutOfMemory` if seabolt cannot allocate the address.
+    pub fn try_new(addr: &str, port: &str) -> Result<Self, BoltError> {
+        let nul = |part: &str| BoltError::InvalidConfig {
+            reason: format!("address {} contains a NUL byte", part),
+        };
+        let addr = CString::new(addr).map_err(|_| nul("host"))?;
+        let port = CString::new(port).map_err(|_| nul("port"))?;
 
         let ptr = unsafe { seabolt_sys::BoltAddress_create(addr.as_ptr(), port.as_ptr()) };
 
         if ptr.is_null() {
-            panic!()
+            Err(BoltError::OutOfMemory)
         } else {
-            Address { ptr }
+            Ok(Address { ptr })
         }
     }
 
+    pub const DEFAULT_PORT: u16 = 7687;
+
+    /// `host` may be an IPv6 literal, with or without the brackets of `[::1]`.
+    pub fn from_parts(host: &str, port: u16) -> Self {
+        Address::new(unbracket(host), &port.to_string())
+    }
+
+    /// Parses `host`, `host:port`, `[v6]` or `[v6]:port`, defaulting the port to 7687. An
+    /// unbracketed IPv6 literal such as `::1` is taken to be all host, as its last colon
+    /// cannot be told apart from a port separator otherwise. A trailing `:` with no port
+    /// after it is an error rather than the default.
+    pub fn parse(s: &str) -> Result<Self, BoltError> {
+        let default_port = Address::DEFAULT_PORT.to_string();
+        let (host, port) = if let Some(rest) = s.strip_prefix('[') {
+            let (host, rest) = rest.split_once(']').ok_or_else(|| BoltError::InvalidConfig {
+                reason: format!("{:?} has an unclosed '['", s),
+            })?;
+            match rest {
+                "" => (host, default_port.as_str()),
+                _ => match rest.strip_prefix(':') {
+                    Some(port) => (host, port),
+                    None => {
+                        return Err(BoltError::InvalidConfig {
+                            reason: format!("{:?} has text after ']' that is not a port", s),
+                        })
+                    }
+                },
+            }
+        } else {
+            match s.split_once(':') {
+                Some((host, port)) if !port.contains(':') => (host, port),
+                _ => (s, default_port.as_str()),
+            }
+        };
+        if port.is_empty() {
+            return Err(BoltError::InvalidConfig {
+                reason: format!("{:?} has an empty port", s),
+            });
+        }
+        Address::try_new(host, port)
+    }
+
+    pub fn with_default_port(host: &str) -> Self {
+        Address::from_parts(host, Address::DEFAULT_PORT)
+    }
+
+    /// Panics if the host is not valid UTF-8, see `try_get_host`.
     pub fn get_host(&self) -> &str {
-        let s = unsafe { CStr::from_ptr(seabolt_sys::BoltAddress_host(self.ptr)) };
-        s.to_str().unwrap()
+        self.try_get_host().expect("address host is not valid UTF-8")
     }
 
+    /// Panics if the port is not valid UTF-8, see `try_get_port`.
     pub fn get_port(&self) -> &str {
-        let s = unsafe { CStr::from_ptr(seabolt_sys::BoltAddress_port(self.ptr)) };
-        s.to_str().unwrap()
+        self.try_get_port().expect("address port is not valid UTF-8")
+    }
+
+    pub fn try_get_host(&self) -> Result<&str, Utf8Error> {
+        self.host().to_str()
+    }
+
+    pub fn try_get_port(&self) -> Result<&str, Utf8Error> {
+        self.port().to_str()
+    }
+
+    pub fn get_host_lossy(&self) -> Cow<'_, str> {
+        self.host().to_string_lossy()
+    }
+
+    pub fn get_port_lossy(&self) -> Cow<'_, str> {
+        self.port().to_string_lossy()
+    }
+
+    fn host(&self) -> &CStr {
+        unsafe { CStr::from_ptr(seabolt_sys::BoltAddress_host(self.ptr)) }
+    }
+
+    fn port(&self) -> &CStr {
+        unsafe { CStr::from_ptr(seabolt_sys::BoltAddress_port(self.ptr)) }
     }
 
     fn as_ptr(&self) -> *mut seabolt_sys::BoltAddress {
@@ -104,6 +337,16 @@ impl Address {
     }
 }
 
+fn unbracket(host: &str) -> &str {
+    host.strip_prefix('[')
+        .and_then(|h| h.strip_suffix(']'))
+        .unwrap_or(host)
+}
+
+impl config::NTTWrap for Address {
+    type ptr = seabolt_sys::BoltAddress;
+}
+
 impl Drop for Address {
     fn drop(&mut self) {
         unsafe { seabolt_sys::BoltAddress_destroy(self.ptr) }
@@ -111,27 +354,226 @@ impl Drop for Address {
 }
 
 #[derive(Debug)]
-pub struct Connector<'a> {
+pub struct Connector {
     ptr: *mut seabolt_sys::BoltConnector,
-    virt: PhantomData<&'a Bolt>,
+    in_use: AtomicUsize,
+    peak: AtomicUsize,
+    draining: AtomicBool,
+    liveness_check_timeout: Option<Duration>,
+    // When each pooled connection was last released, keyed by its address.
+    idle_since: Mutex<HashMap<usize, Instant>>,
+    liveness_failures: AtomicUsize,
+    resolver: Option<routing::Resolver>,
+    #[cfg(feature = "tracing")]
+    trace_options: TraceOptions,
+    // Dropped after `BoltConnector_destroy`, which `Drop` calls first.
+    _runtime: Arc<Runtime>,
+}
+
+/// seabolt does not expose its pool state, so these are tracked from the acquire and
+/// release calls made through a `Connector`. `in_use` is how many connections are checked
+/// out now, counting any `acquire` still waiting on the pool, and `peak_in_use` the most
+/// that have been at once. How many connections the pool holds open cannot be told from
+/// this, as seabolt closes pooled connections on its own (past their maximum lifetime, or
+/// once found dead). `liveness_failures` counts idle connections that did not answer the
+/// liveness check.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct PoolMetrics {
+    pub in_use: usize,
+    pub peak_in_use: usize,
+    pub liveness_failures: usize,
 }
 
-impl<'a> Connector<'a> {
-    fn new(addr: &Address, auth: &Auth, config: &Config) -> Self {
+impl Connector {
+    fn new(runtime: Arc<Runtime>, addr: &Address, auth: &Auth, config: &Config) -> Self {
         let ptr = unsafe {
             seabolt_sys::BoltConnector_create(addr.as_ptr(), auth.as_ptr(), config.as_ptr())
         };
         Connector {
             ptr,
-            virt: PhantomData,
+            in_use: AtomicUsize::new(0),
+            peak: AtomicUsize::new(0),
+            draining: AtomicBool::new(false),
+            liveness_check_timeout: config.get_liveness_check_timeout(),
+            idle_since: Mutex::new(HashMap::new()),
+            liveness_failures: AtomicUsize::new(0),
+            resolver: None,
+            #[cfg(feature = "tracing")]
+            trace_options: TraceOptions::default(),
+            _runtime: runtime,
         }
     }
 
-    pub fn acquire() {}
+    /// With a liveness check timeout configured, a pooled connection that has sat idle for
+    /// longer is sent a RESET first; one that fails it is given back and another acquired.
+    pub fn acquire(&self, mode: AccessMode) -> Result<Connection<'_>, BoltError> {
+        let mode = mode.as_idx()? as i32;
+        loop {
+            let ptr = self.acquire_ptr(mode)?;
+            let stale = self.is_stale(ptr);
+            let conn = Connection::new(ptr, self);
+            if !stale || conn.reset().is_ok() {
+                return Ok(conn);
+            }
+            self.liveness_failures.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    /// Like `acquire`, but the connection holds a share of the connector instead of
+    /// borrowing it, so it can be moved to another thread or task on its own.
+    pub fn acquire_owned(
+        self: &Arc<Self>,
+        mode: AccessMode,
+    ) -> Result<Connection<'static>, BoltError> {
+        Ok(self.acquire(mode)?.into_shared(self.clone()))
+    }
+
+    /// Opens a session for `mode` against `db`, or the server's default database when `db`
+    /// is `None`; `session(None, AccessMode::default())` is the usual single-database write
+    /// session. seabolt only speaks Bolt v1 to v3, which cannot select a database, so naming
+    /// one fails with `BoltError::InvalidConfig` rather than silently using the default.
+    pub fn session(&self, db: Option<&str>, mode: AccessMode) -> Result<Session<'_>, BoltError> {
+        if let Some(db) = db {
+            return Err(BoltError::InvalidConfig {
+                reason: format!("cannot select database {:?} over Bolt v1 to v3", db),
+            });
+        }
+        Session::new(self, mode)
+    }
+
+    /// Opens up to `n` connections at once and returns them to the pool, so that the first
+    /// requests do not pay for connecting, and so that a bad address or bad credentials
+    /// fail at startup. Fails only if not even one connection could be opened; otherwise
+    /// returns how many were, which is less than `n` if the pool is smaller or an
+    /// acquisition failed part way.
+    pub fn warm_up(&self, n: usize) -> Result<usize, BoltError> {
+        let mut conns = Vec::with_capacity(n);
+        for _ in 0..n {
+            match self.acquire(AccessMode::Write) {
+                Ok(conn) => conns.push(conn),
+                Err(e) if conns.is_empty() => return Err(e),
+                Err(_) => break,
+            }
+        }
+        Ok(conns.len())
+    }
+
+    /// Stops handing out connections, then waits up to `timeout` for those already checked
+    /// out to be released, failing with `BoltError::Timeout` if some are still in use. Every
+    /// later `acquire` fails with `BoltError::Draining`.
+    pub fn drain(&self, timeout: Duration) -> Result<(), BoltError> {
+        self.draining.store(true, Ordering::SeqCst);
+        let deadline = Instant::now() + timeout;
+        // Releases happen on other threads with no hook to wake us, so poll the counter.
+        while self.in_use.load(Ordering::SeqCst) > 0 {
+            if Instant::now() >= deadline {
+                return Err(BoltError::Timeout);
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        Ok(())
+    }
+
+    fn acquire_ptr(&self, mode: i32) -> Result<*mut seabolt_sys::BoltConnection, BoltError> {
+        // Counted before `draining` is checked, so a drain that starts in between still
+        // waits for this acquire.
+        let in_use = self.in_use.fetch_add(1, Ordering::SeqCst) + 1;
+        if self.draining.load(Ordering::SeqCst) {
+            self.in_use.fetch_sub(1, Ordering::SeqCst);
+            return Err(BoltError::Draining);
+        }
+        let status = unsafe { seabolt_sys::BoltStatus_create() };
+        let ptr = unsafe { seabolt_sys::BoltConnector_acquire(self.ptr, mode, status) };
+        let res = if ptr.is_null() {
+            self.in_use.fetch_sub(1, Ordering::SeqCst);
+            Err(unsafe { BoltError::from_status(status) })
+        } else if self.draining.load(Ordering::SeqCst) {
+            // A drain started while seabolt was handing the connection out.
+            self.release(ptr);
+            Err(BoltError::Draining)
+        } else {
+            self.peak.fetch_max(in_use, Ordering::SeqCst);
+            Ok(ptr)
+        };
+        unsafe { seabolt_sys::BoltStatus_destroy(status) };
+        res
+    }
+
+    fn is_stale(&self, conn: *mut seabolt_sys::BoltConnection) -> bool {
+        let timeout = match self.liveness_check_timeout {
+            Some(timeout) => timeout,
+            None => return false,
+        };
+        let released = self.idle_since.lock().unwrap().remove(&(conn as usize));
+        released.map_or(false, |at| at.elapsed() > timeout)
+    }
+
+    pub fn pool_metrics(&self) -> PoolMetrics {
+        let in_use = self.in_use.load(Ordering::SeqCst);
+        PoolMetrics {
+            in_use,
+            peak_in_use: self.peak.load(Ordering::SeqCst).max(in_use),
+            liveness_failures: self.liveness_failures.load(Ordering::SeqCst),
+        }
+    }
+
+    fn release(&self, conn: *mut seabolt_sys::BoltConnection) {
+        unsafe { seabolt_sys::BoltConnector_release(self.ptr, conn) };
+        if self.liveness_check_timeout.is_some() {
+            let mut idle_since = self.idle_since.lock().unwrap();
+            idle_since.insert(conn as usize, Instant::now());
+        }
+        self.in_use.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl Connector {
+    /// Acquires a connection on tokio's blocking thread pool. This is not asynchronous IO,
+    /// it only stops a slow acquire from stalling the executor. The blocking task may
+    /// outlive the returned future, so it takes its own share of the connector, and the
+    /// connection holds one too, as from `acquire_owned`:
+    ///
+    /// ```no_run
+    /// # async fn example() -> Result<(), seabolt::BoltError> {
+    /// use seabolt::{AccessMode, Driver, Value};
+    ///
+    /// let driver = Driver::builder("bolt://localhost:7687")
+    ///     .with_basic_auth("neo4j", "password")
+    ///     .build()?;
+    /// let conn = driver.connector().acquire_async(AccessMode::Read).await?;
+    /// let (conn, rows, summary) = conn
+    ///     .run_async("RETURN 1".to_string(), Value::from_null())
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn acquire_async(
+        self: &Arc<Self>,
+        mode: AccessMode,
+    ) -> Result<Connection<'static>, BoltError> {
+        let connector = self.clone();
+        tokio::task::spawn_blocking(move || connector.acquire_owned(mode))
+            .await
+            .unwrap_or_else(|e| std::panic::resume_unwind(e.into_panic()))
+    }
 }
 
-impl<'a> Drop for Connector<'a> {
+// SAFETY: seabolt guards each of a connector's pools with its own mutex (and the routing
+// table with a read-write lock), so BoltConnector_acquire and BoltConnector_release may be
+// called from any number of threads at once through a shared Connector. The state kept on
+// the Rust side is atomics or behind a Mutex, and each Connection handed out is still owned
+// by a single caller.
+unsafe impl Send for Connector {}
+unsafe impl Sync for Connector {}
+
+impl Drop for Connector {
     fn drop(&mut self) {
+        debug_assert_eq!(
+            self.in_use.load(Ordering::SeqCst),
+            0,
+            "Connector dropped while a Connection is checked out"
+        );
         unsafe {
             seabolt_sys::BoltConnector_destroy(self.ptr);
         }
@@ -144,6 +586,26 @@ impl Auth {
     pub(crate) fn as_ptr(&self) -> *mut seabolt_sys::BoltValue {
         self.0.as_ptr()
     }
+
+    /// The authentication scheme, e.g. "basic".
+    pub fn scheme(&self) -> Option<&str> {
+        self.0.get_str("scheme")
+    }
+}
+
+// Secrets are masked so that an Auth can be logged without leaking them.
+impl fmt::Debug for Auth {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut s = f.debug_struct("Auth");
+        for (k, v) in self.0.iter_dict() {
+            let text = match k {
+                "credentials" | "password" => "\"***\"".to_string(),
+                _ => diff::describe(&v),
+            };
+            s.field(k, &format_args!("{}", text));
+        }
+        s.finish()
+    }
 }
 
 pub fn basic_auth(username: &str, password: &str, realm: Option<&str>) -> Auth {
@@ -163,3 +625,127 @@ pub fn basic_auth(username: &str, password: &str, realm: Option<&str>) -> Auth {
         ))
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    lazy_static! {
+        // Only one Bolt may be initialized at a time, so the tests that need one take turns.
+        static ref BOLT: Mutex<()> = Mutex::new(());
+    }
+
+    fn connector(bolt: &Bolt) -> Connector {
+        let address = Address::new("localhost", "7687");
+        let auth = basic_auth("neo4j", "password", None);
+        bolt.create_connector(&address, &auth, &Config::build().finish())
+    }
+
+    fn parts(s: &str) -> (String, String) {
+        let addr = Address::parse(s).unwrap();
+        (addr.get_host().to_string(), addr.get_port().to_string())
+    }
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn connector_is_send_sync() {
+        assert_send_sync::<Connector>();
+        assert_send_sync::<Arc<Connector>>();
+    }
+
+    #[cfg(feature = "log")]
+    #[test]
+    fn logger_is_removed_from_config() {
+        let _guard = BOLT.lock().unwrap_or_else(|e| e.into_inner());
+        let mut bolt = Bolt::init().unwrap();
+        bolt.set_logger();
+        let config = Config::build().finish();
+        let address = Address::new("localhost", "7687");
+        let connector = bolt.create_connector(&address, &basic_auth("neo4j", "", None), &config);
+        assert!(unsafe { seabolt_sys::BoltConfig_get_log(config.as_ptr()) }.is_null());
+        drop(connector);
+    }
+
+    #[test]
+    fn bolt_keeps_seabolt_running() {
+        let _guard = BOLT.lock().unwrap_or_else(|e| e.into_inner());
+        let bolt = Bolt::init().unwrap();
+        drop(connector(&bolt));
+        assert!(matches!(Bolt::init(), Err(BoltError::AlreadyInitialized)));
+        drop(bolt);
+        drop(Bolt::init().unwrap());
+    }
+
+    #[test]
+    fn connector_keeps_seabolt_running() {
+        let _guard = BOLT.lock().unwrap_or_else(|e| e.into_inner());
+        let bolt = Bolt::init().unwrap();
+        let connector = connector(&bolt);
+        drop(bolt);
+        assert!(matches!(Bolt::init(), Err(BoltError::AlreadyInitialized)));
+        drop(connector);
+        drop(Bolt::init().unwrap());
+    }
+
+    #[test]
+    fn parse_host_and_port() {
+        assert_eq!(parts("localhost"), ("localhost".into(), "7687".into()));
+        assert_eq!(
+            parts("db.example.com:7688"),
+            ("db.example.com".into(), "7688".into())
+        );
+        let invalid = |s| matches!(Address::parse(s), Err(BoltError::InvalidConfig { .. }));
+        assert!(invalid("localhost:"));
+        assert!(invalid("[::1]:"));
+    }
+
+    #[test]
+    fn parse_ipv6() {
+        assert_eq!(parts("::1"), ("::1".into(), "7687".into()));
+        assert_eq!(
+            parts("2001:db8:85a3::8a2e:370:7334"),
+            ("2001:db8:85a3::8a2e:370:7334".into(), "7687".into())
+        );
+        assert_eq!(parts("[::1]:7688"), ("::1".into(), "7688".into()));
+        assert_eq!(parts("[::1]"), ("::1".into(), "7687".into()));
+    }
+
+    #[test]
+    fn parse_malformed_brackets() {
+        let invalid = |s| matches!(Address::parse(s), Err(BoltError::InvalidConfig { .. }));
+        assert!(invalid("[::1"));
+        assert!(invalid("[::1]7687"));
+        assert!(invalid("[::1]x"));
+    }
+
+    #[test]
+    fn auth_scheme() {
+        let auth = basic_auth("neo4j", "password", None);
+        assert_eq!(auth.scheme(), Some("basic"));
+    }
+
+    #[test]
+    fn from_parts_unbrackets() {
+        let addr = Address::from_parts("[::1]", 7687);
+        assert_eq!(addr.get_host(), "::1");
+        assert_eq!(addr.get_port(), "7687");
+        assert_eq!(Address::from_parts("::1", 7687).get_host(), "::1");
+    }
+
+    // Needs a server to hand out a connection, see tests/server.rs.
+    #[test]
+    #[ignore]
+    fn connection_is_released_on_drop() {
+        let _guard = BOLT.lock().unwrap_or_else(|e| e.into_inner());
+        let bolt = Bolt::init().unwrap();
+        let connector = connector(&bolt);
+        let conn = connector.acquire(AccessMode::Read).unwrap();
+        assert_eq!(connector.pool_metrics().in_use, 1);
+        drop(conn);
+        assert_eq!(connector.pool_metrics().in_use, 0);
+        drop(connector);
+        drop(bolt);
+        drop(Bolt::init().unwrap());
+    }
+}
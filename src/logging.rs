@@ -0,0 +1,50 @@
+use std::{
+    ffi::CStr,
+    os::raw::{c_char, c_void},
+    ptr,
+};
+
+macro_rules! forward {
+    ($name:ident, $level:ident) => {
+        unsafe extern "C" fn $name(_state: *mut c_void, message: *const c_char) {
+            log::$level!(target: "seabolt", "{}", CStr::from_ptr(message).to_string_lossy());
+        }
+    };
+}
+
+forward!(error, error);
+forward!(warning, warn);
+forward!(info, info);
+forward!(debug, debug);
+
+#[derive(Debug)]
+pub(crate) struct Logger {
+    ptr: *mut seabolt_sys::BoltLog,
+}
+
+impl Logger {
+    pub(crate) fn new() -> Self {
+        let ptr = unsafe { seabolt_sys::BoltLog_create(ptr::null_mut()) };
+        unsafe {
+            seabolt_sys::BoltLog_set_error_func(ptr, Some(error));
+            seabolt_sys::BoltLog_set_warning_func(ptr, Some(warning));
+            seabolt_sys::BoltLog_set_info_func(ptr, Some(info));
+            seabolt_sys::BoltLog_set_debug_func(ptr, Some(debug));
+        }
+        Logger { ptr }
+    }
+
+    pub(crate) fn as_ptr(&self) -> *mut seabolt_sys::BoltLog {
+        self.ptr
+    }
+}
+
+impl Drop for Logger {
+    fn drop(&mut self) {
+        unsafe { seabolt_sys::BoltLog_destroy(self.ptr) }
+    }
+}
+
+// The callbacks carry no state and the log facade is thread-safe.
+unsafe impl Send for Logger {}
+unsafe impl Sync for Logger {}
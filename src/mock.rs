@@ -0,0 +1,156 @@
+use std::{cell::RefCell, collections::VecDeque, sync::Arc};
+
+use crate::{BoltError, ConnectionLike, InputPosition, Record, Summary, Value};
+
+#[derive(Debug)]
+enum Response {
+    Rows {
+        fields: Arc<[String]>,
+        rows: Vec<Vec<Value>>,
+        metadata: Value,
+    },
+    Failure(BoltError),
+}
+
+#[derive(Debug)]
+struct Expectation {
+    cypher: String,
+    response: Response,
+}
+
+/// A scripted stand-in for a `Connection`. Queries must arrive in the order they were
+/// expected; anything else panics, as does dropping the mock with expectations unmet.
+#[derive(Debug)]
+pub struct MockConnection {
+    expected: RefCell<VecDeque<Expectation>>,
+    received: RefCell<Vec<(String, Value)>>,
+}
+
+impl MockConnection {
+    pub fn build() -> MockConnectionBuilder {
+        MockConnectionBuilder {
+            expected: VecDeque::new(),
+        }
+    }
+
+    /// Every query run so far, with its parameters.
+    pub fn received(&self) -> Vec<(String, Value)> {
+        self.received.borrow().clone()
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.expected.borrow().is_empty()
+    }
+}
+
+impl ConnectionLike for MockConnection {
+    fn run_collect(
+        &self,
+        cypher: &str,
+        params: Value,
+    ) -> Result<(Vec<Record>, Summary), BoltError> {
+        self.received
+            .borrow_mut()
+            .push((cypher.to_string(), params));
+        let expectation = match self.expected.borrow_mut().pop_front() {
+            Some(e) => e,
+            None => panic!("unexpected query: {}", cypher),
+        };
+        assert_eq!(
+            expectation.cypher, cypher,
+            "query does not match the next expectation"
+        );
+        match expectation.response {
+            Response::Rows {
+                fields,
+                rows,
+                metadata,
+            } => {
+                let records = rows
+                    .into_iter()
+                    .map(|row| Record::new(fields.clone(), Value::from_list(row)))
+                    .collect();
+                Ok((records, Summary::new(Value::from_null(), metadata)))
+            }
+            Response::Failure(e) => Err(e),
+        }
+    }
+
+    fn begin(&self, _metadata: Option<Value>) -> Result<(), BoltError> {
+        Ok(())
+    }
+
+    fn commit(&self) -> Result<(), BoltError> {
+        Ok(())
+    }
+
+    fn rollback(&self) -> Result<(), BoltError> {
+        Ok(())
+    }
+}
+
+impl Drop for MockConnection {
+    fn drop(&mut self) {
+        if !std::thread::panicking() {
+            if let Some(e) = self.expected.borrow().front() {
+                panic!("expected query was never run: {}", e.cypher);
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct MockConnectionBuilder {
+    expected: VecDeque<Expectation>,
+}
+
+impl MockConnectionBuilder {
+    pub fn finish(self) -> MockConnection {
+        MockConnection {
+            expected: RefCell::new(self.expected),
+            received: RefCell::new(Vec::new()),
+        }
+    }
+
+    pub fn with_query(self, cypher: &str, fields: &[&str], rows: Vec<Vec<Value>>) -> Self {
+        self.with_query_metadata(cypher, fields, rows, Value::from_null())
+    }
+
+    /// `metadata` is what the summary is built from, e.g. a dictionary with `stats`.
+    pub fn with_query_metadata(
+        self,
+        cypher: &str,
+        fields: &[&str],
+        rows: Vec<Vec<Value>>,
+        metadata: Value,
+    ) -> Self {
+        let fields = fields.iter().map(|f| f.to_string()).collect();
+        self.expect(
+            cypher,
+            Response::Rows {
+                fields,
+                rows,
+                metadata,
+            },
+        )
+    }
+
+    pub fn with_failure(self, cypher: &str, code: &str, message: &str) -> Self {
+        self.expect(
+            cypher,
+            Response::Failure(BoltError::Server {
+                code: code.to_string(),
+                message: message.to_string(),
+                position: InputPosition::from_message(message),
+            }),
+        )
+    }
+
+    fn expect(mut self, cypher: &str, response: Response) -> Self {
+        self.expected.push_back(Expectation {
+            cypher: cypher.to_string(),
+            response,
+        });
+        self
+    }
+}
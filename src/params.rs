@@ -0,0 +1,156 @@
+use std::{convert::TryFrom, time::Duration};
+
+use crate::{BoltError, Bookmark, Value};
+
+#[derive(Debug, Default)]
+pub struct Params {
+    entries: Vec<(String, Value)>,
+}
+
+impl Params {
+    pub fn new() -> Self {
+        Params::default()
+    }
+
+    pub fn insert<K: Into<String>, V: Into<Value>>(mut self, key: K, value: V) -> Self {
+        self.entries.push((key.into(), value.into()));
+        self
+    }
+
+    pub fn build(self) -> Value {
+        Value::from_dict(self.entries)
+    }
+}
+
+/// A Cypher template together with its parameters, to keep values out of the query text:
+/// `Query::new("MATCH (n) WHERE n.id IN $ids RETURN n").param("ids", ids).build()?` gives
+/// the `(cypher, params)` pair for `run`.
+#[derive(Debug)]
+pub struct Query {
+    cypher: String,
+    params: Params,
+    strict: bool,
+}
+
+impl Query {
+    pub fn new<S: Into<String>>(cypher: S) -> Self {
+        Query {
+            cypher: cypher.into(),
+            params: Params::new(),
+            strict: false,
+        }
+    }
+
+    pub fn param<K: Into<String>, V: Into<Value>>(mut self, key: K, value: V) -> Self {
+        self.params = self.params.insert(key, value);
+        self
+    }
+
+    /// Makes `build` fail with `BoltError::UnboundParameter` if the template refers to a
+    /// `$name` that has not been given a value.
+    pub fn strict(mut self) -> Self {
+        self.strict = true;
+        self
+    }
+
+    pub fn build(self) -> Result<(String, Value), BoltError> {
+        if self.strict {
+            if let Some(name) = template_params(&self.cypher)
+                .into_iter()
+                .find(|name| !self.params.entries.iter().any(|(k, _)| k == name))
+            {
+                return Err(BoltError::UnboundParameter {
+                    name: name.to_string(),
+                });
+            }
+        }
+        Ok((self.cypher, self.params.build()))
+    }
+}
+
+// The `$name` references in a template, skipping over string literals and escaped names.
+fn template_params(cypher: &str) -> Vec<&str> {
+    let bytes = cypher.as_bytes();
+    let mut names = Vec::new();
+    let mut quote = None;
+    let mut i = 0;
+    while i < bytes.len() {
+        let b = bytes[i];
+        match quote {
+            Some(_) if b == b'\\' => i += 1,
+            Some(q) if b == q => quote = None,
+            Some(_) => {}
+            None if b == b'\'' || b == b'"' || b == b'`' => quote = Some(b),
+            None if b == b'$' => {
+                let start = i + 1;
+                let len = bytes[start..]
+                    .iter()
+                    .take_while(|c| c.is_ascii_alphanumeric() || **c == b'_')
+                    .count();
+                if len > 0 {
+                    names.push(&cypher[start..start + len]);
+                }
+                i = start + len;
+                continue;
+            }
+            None => {}
+        }
+        i += 1;
+    }
+    names
+}
+
+/// The extra fields seabolt can attach to a RUN or BEGIN request, for `run_with_metadata`
+/// and `begin`. The access mode is not among them, it is fixed by `Connector::acquire`.
+#[derive(Debug, Default)]
+pub struct RunMetadata {
+    bookmarks: Vec<Bookmark>,
+    tx_timeout: Option<Duration>,
+    tx_metadata: Option<Value>,
+}
+
+impl RunMetadata {
+    pub fn new() -> Self {
+        RunMetadata::default()
+    }
+
+    pub fn with_bookmark(mut self, bookmark: Bookmark) -> Self {
+        self.bookmarks.push(bookmark);
+        self
+    }
+
+    pub fn with_tx_timeout(mut self, timeout: Duration) -> Self {
+        self.tx_timeout = Some(timeout);
+        self
+    }
+
+    /// Arbitrary values the server logs with the transaction, e.g. in
+    /// `dbms.listTransactions`. Sending anything but a dictionary fails with
+    /// `BoltError::TypeMismatch`.
+    pub fn with_tx_metadata(mut self, metadata: Value) -> Self {
+        self.tx_metadata = Some(metadata);
+        self
+    }
+
+    pub fn build(self) -> Value {
+        let mut params = Params::new();
+        if !self.bookmarks.is_empty() {
+            let bookmarks = self.bookmarks.into_iter().map(Value::from);
+            params = params.insert("bookmarks", bookmarks.collect::<Vec<_>>());
+        }
+        if let Some(timeout) = self.tx_timeout {
+            let ms = i64::try_from(timeout.as_millis()).unwrap_or(i64::MAX);
+            params = params.insert("tx_timeout", ms);
+        }
+        if let Some(metadata) = self.tx_metadata {
+            params = params.insert("tx_metadata", metadata);
+        }
+        params.build()
+    }
+}
+
+impl From<RunMetadata> for Value {
+    fn from(metadata: RunMetadata) -> Self {
+        metadata.build()
+    }
+}
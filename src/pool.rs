@@ -0,0 +1,64 @@
+use crate::Value;
+
+/// Recycles `Value` allocations for tight loops that build many short-lived values, such as
+/// the parameter dictionaries of a bulk import. A returned value is reset to null with
+/// `BoltValue_format_as_Null`, which releases its contents (nested values included), and
+/// handed out again instead of being destroyed. It only saves seabolt's allocation of the
+/// value itself, so it helps when many small values are built, not a few large ones;
+/// `benches/value_pool.rs` measures the difference.
+#[derive(Debug)]
+pub struct ValuePool {
+    free: Vec<Value>,
+    max_idle: usize,
+}
+
+impl ValuePool {
+    /// Keeps at most `max_idle` values around; any beyond that are destroyed as usual.
+    pub fn new(max_idle: usize) -> Self {
+        ValuePool {
+            free: Vec::with_capacity(max_idle),
+            max_idle,
+        }
+    }
+
+    /// A null value, reused if one is idle.
+    pub fn take(&mut self) -> Value {
+        self.free.pop().unwrap_or_else(Value::from_null)
+    }
+
+    pub fn give(&mut self, mut v: Value) {
+        if self.free.len() < self.max_idle {
+            v.null();
+            self.free.push(v);
+        }
+    }
+
+    pub fn idle(&self) -> usize {
+        self.free.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{value_list, value_map, ValueType};
+
+    #[test]
+    fn given_values_come_back_cleared() {
+        let mut pool = ValuePool::new(1);
+        pool.give(value_map! { "id" => 1, "tags" => value_list!["a", "b"] });
+        assert_eq!(pool.idle(), 1);
+        let v = pool.take();
+        assert_eq!(v.get_type(), ValueType::Null);
+        assert_eq!(v, Value::from_null());
+        assert_eq!(pool.idle(), 0);
+    }
+
+    #[test]
+    fn keeps_at_most_max_idle() {
+        let mut pool = ValuePool::new(1);
+        pool.give(Value::from(1));
+        pool.give(Value::from(2));
+        assert_eq!(pool.idle(), 1);
+    }
+}
@@ -0,0 +1,10 @@
+pub use crate::{
+    basic_auth,
+    config::{
+        ConfigBuilder, Scheme, SocketOptions, SocketOptionsBuilder, Transport, Trust, TrustBuilder,
+    },
+    AccessMode, Address, Addresses, Auth, Bolt, BoltError, Bookmark, Config, Connection,
+    ConnectionLike, Connector, Driver, FromRecord, Notification, Params, QueryHandle, Record,
+    Records, ResultCursor, RetryPolicy, Session, Summary, SummaryCounters, Tx, Value, ValueRef,
+    ValueType,
+};
@@ -0,0 +1,135 @@
+use std::convert::TryFrom;
+
+use crate::{BoltError, Record, Value, ValueType};
+
+/// Builds a value from a single result row. `#[derive(FromRecord)]`, behind the `derive`
+/// feature, implements this for a struct by reading each field from the column of the same
+/// name.
+pub trait FromRecord: Sized {
+    fn from_record(record: &Record) -> Result<Self, BoltError>;
+}
+
+/// A type that can be read out of one column of a `Record`.
+pub trait FromColumn: Sized {
+    fn from_column(v: &Value) -> Result<Self, BoltError>;
+
+    /// What a column absent from the row reads as, or `None` if that is an error.
+    fn missing() -> Option<Self> {
+        None
+    }
+}
+
+macro_rules! from_column {
+    ($($t:ty),+) => {
+        $(impl FromColumn for $t {
+            fn from_column(v: &Value) -> Result<Self, BoltError> {
+                <$t>::try_from(v)
+            }
+        })+
+    };
+}
+
+from_column!(bool, i64, f64, String);
+
+impl FromColumn for Value {
+    fn from_column(v: &Value) -> Result<Self, BoltError> {
+        Ok(v.clone())
+    }
+}
+
+/// A nullable column: both null and a missing column read as `None`.
+impl<T: FromColumn> FromColumn for Option<T> {
+    fn from_column(v: &Value) -> Result<Self, BoltError> {
+        match v.get_type() {
+            ValueType::Null => Ok(None),
+            _ => T::from_column(v).map(Some),
+        }
+    }
+
+    fn missing() -> Option<Self> {
+        Some(None)
+    }
+}
+
+impl Record {
+    /// Reads the named column as `T`, failing if the column is absent (unless `T` is an
+    /// `Option`) or holds a value of the wrong type.
+    pub fn get_as<T: FromColumn>(&self, key: &str) -> Result<T, BoltError> {
+        match self.get(key) {
+            Some(v) => T::from_column(&v),
+            None => T::missing().ok_or_else(|| BoltError::MissingColumn {
+                name: key.to_string(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{convert::TryInto, sync::Arc};
+
+    use super::*;
+    use crate::value_list;
+
+    fn record() -> Record {
+        let fields: Arc<[String]> = vec!["name".to_string(), "age".into(), "nick".into()].into();
+        Record::new(fields, value_list!["Bob", 42, Value::from_null()])
+    }
+
+    #[test]
+    fn try_from_scalars() {
+        assert!(bool::try_from(Value::from(true)).unwrap());
+        assert_eq!(i64::try_from(&Value::from(42)).unwrap(), 42);
+        assert_eq!(f64::try_from(Value::from(1.5)).unwrap(), 1.5);
+        assert_eq!(String::try_from(Value::from("Bob")).unwrap(), "Bob");
+        let n: i64 = Value::from(7).try_into().unwrap();
+        assert_eq!(n, 7);
+    }
+
+    #[test]
+    fn try_from_mismatch() {
+        let mismatch = |found| BoltError::TypeMismatch {
+            expected: ValueType::Integer,
+            found,
+        };
+        assert_eq!(
+            i64::try_from(Value::from("42")),
+            Err(mismatch(ValueType::String))
+        );
+        // Floats are never narrowed, even when they hold a whole number.
+        assert_eq!(
+            i64::try_from(Value::from(42.0)),
+            Err(mismatch(ValueType::Float))
+        );
+        assert!(bool::try_from(Value::from(1)).is_err());
+        assert!(String::try_from(Value::from_null()).is_err());
+    }
+
+    #[test]
+    fn integers_widen_to_floats() {
+        assert_eq!(f64::try_from(Value::from(3)).unwrap(), 3.0);
+    }
+
+    #[test]
+    fn get_as() {
+        let record = record();
+        assert_eq!(record.get_as::<String>("name").unwrap(), "Bob");
+        assert_eq!(record.get_as::<i64>("age").unwrap(), 42);
+        assert_eq!(record.get_as::<Option<String>>("nick").unwrap(), None);
+        assert_eq!(record.get_as::<Option<i64>>("height").unwrap(), None);
+        assert_eq!(
+            record.get_as::<i64>("name"),
+            Err(BoltError::TypeMismatch {
+                expected: ValueType::Integer,
+                found: ValueType::String,
+            })
+        );
+        assert_eq!(
+            record.get_as::<String>("height"),
+            Err(BoltError::MissingColumn {
+                name: "height".to_string()
+            })
+        );
+        assert!(record.get_as::<String>("nick").is_err());
+    }
+}
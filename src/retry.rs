@@ -0,0 +1,82 @@
+use std::{thread, time::Duration};
+
+use crate::BoltError;
+
+/// How often, and how patiently, a unit of work is retried after a transient failure. The
+/// wait starts at the initial backoff and is multiplied after every attempt, up to the
+/// maximum backoff.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    multiplier: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 5,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(10),
+            multiplier: 2.0,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// `max_attempts` counts the first try, so 1 never retries.
+    pub fn new(max_attempts: u32) -> Self {
+        RetryPolicy {
+            max_attempts: max_attempts.max(1),
+            ..RetryPolicy::default()
+        }
+    }
+
+    pub fn with_initial_backoff(mut self, backoff: Duration) -> Self {
+        self.initial_backoff = backoff;
+        self
+    }
+
+    pub fn with_max_backoff(mut self, backoff: Duration) -> Self {
+        self.max_backoff = backoff;
+        self
+    }
+
+    pub fn with_multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        let secs = self.initial_backoff.as_secs_f64() * self.multiplier.powi(attempt as i32);
+        // Also catches an overflowing or NaN product.
+        if secs < self.max_backoff.as_secs_f64() {
+            Duration::from_secs_f64(secs.max(0.0))
+        } else {
+            self.max_backoff
+        }
+    }
+}
+
+/// Calls `f` until it succeeds, fails with an error that is not retryable, or the policy
+/// runs out of attempts, sleeping between attempts. The last error is returned.
+pub fn retry<T, F>(policy: &RetryPolicy, mut f: F) -> Result<T, BoltError>
+where
+    F: FnMut() -> Result<T, BoltError>,
+{
+    let mut attempt = 0;
+    loop {
+        match f() {
+            Err(e) if e.is_retryable() && attempt + 1 < policy.max_attempts => {
+                thread::sleep(policy.backoff(attempt));
+                attempt += 1;
+            }
+            res => return res,
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use std::{convert::TryFrom, os::raw::c_void, ptr, slice, time::Duration};
+
+use crate::{AccessMode, Address, BoltError, Connector, Params, Value, ValueType};
+
+/// Seed routers for a `neo4j://` cluster. The first address is the one the connector is
+/// created with; when seabolt resolves it for routing every seed is handed back, in order,
+/// so a router that is down at startup is skipped over.
+#[derive(Debug)]
+pub struct Addresses {
+    addrs: Vec<Address>,
+}
+
+impl Addresses {
+    /// Panics if `addrs` is empty.
+    pub fn new(addrs: Vec<Address>) -> Self {
+        assert!(!addrs.is_empty(), "at least one seed address is required");
+        Addresses { addrs }
+    }
+
+    /// Parses a comma-separated `host:port` list, as the official drivers accept for their
+    /// routers, see `Address::parse`. A missing port defaults to 7687. Panics if the list is
+    /// empty or an address is malformed.
+    pub fn parse(list: &str) -> Self {
+        let addrs = list
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(|s| Address::parse(s).unwrap_or_else(|e| panic!("invalid address: {}", e)))
+            .collect();
+        Addresses::new(addrs)
+    }
+
+    pub fn push(&mut self, addr: Address) {
+        self.addrs.push(addr);
+    }
+
+    pub fn primary(&self) -> &Address {
+        &self.addrs[0]
+    }
+
+    pub fn iter(&self) -> slice::Iter<'_, Address> {
+        self.addrs.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.addrs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.addrs.is_empty()
+    }
+}
+
+unsafe extern "C" fn resolve(
+    state: *mut c_void,
+    _address: *mut seabolt_sys::BoltAddress,
+    resolved: *mut seabolt_sys::BoltAddressSet,
+) {
+    let seeds = &*(state as *const Addresses);
+    for addr in seeds.iter() {
+        seabolt_sys::BoltAddressSet_add(resolved, addr.as_ptr());
+    }
+}
+
+/// Owns both the seabolt resolver and the seeds its callback reads, so it has to outlive
+/// the connector it was installed on.
+#[derive(Debug)]
+pub(crate) struct Resolver {
+    ptr: *mut seabolt_sys::BoltAddressResolver,
+    seeds: Box<Addresses>,
+}
+
+impl Resolver {
+    pub(crate) fn new(seeds: Addresses) -> Self {
+        let seeds = Box::new(seeds);
+        let state = &*seeds as *const Addresses as *mut c_void;
+        let ptr = unsafe { seabolt_sys::BoltAddressResolver_create(state, Some(resolve)) };
+        Resolver { ptr, seeds }
+    }
+
+    pub(crate) fn seeds(&self) -> &Addresses {
+        &self.seeds
+    }
+
+    /// Installs the resolver on `config` for the duration of `f`, so the config never holds
+    /// a pointer that outlives it.
+    pub(crate) fn install<T>(
+        &self,
+        config: *mut seabolt_sys::BoltConfig,
+        f: impl FnOnce() -> T,
+    ) -> T {
+        unsafe { seabolt_sys::BoltConfig_set_address_resolver(config, self.ptr) };
+        let result = f();
+        unsafe { seabolt_sys::BoltConfig_set_address_resolver(config, ptr::null_mut()) };
+        result
+    }
+}
+
+impl Drop for Resolver {
+    fn drop(&mut self) {
+        unsafe { seabolt_sys::BoltAddressResolver_destroy(self.ptr) }
+    }
+}
+
+// The callback only reads the seeds, which are never mutated once boxed.
+unsafe impl Send for Resolver {}
+unsafe impl Sync for Resolver {}
+
+/// A cluster's routing table as the server reports it, see `Connector::routing_table`.
+/// Addresses are `host:port` strings exactly as advertised.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct RoutingTable {
+    pub ttl: Duration,
+    pub routers: Vec<String>,
+    pub readers: Vec<String>,
+    pub writers: Vec<String>,
+}
+
+impl Connector {
+    /// Asks a cluster member for its current routing table, for `db` on Neo4j 4 or for the
+    /// only database when `db` is `None`. It is fetched afresh rather than read from seabolt,
+    /// which keeps its own copy private.
+    pub fn routing_table(&self, db: Option<&str>) -> Result<RoutingTable, BoltError> {
+        let conn = self.acquire(AccessMode::Read)?;
+        let context = Value::from_dict(Vec::new());
+        let (cypher, params) = match db {
+            Some(db) => (
+                "CALL dbms.routing.getRoutingTable($context, $database)",
+                Params::new()
+                    .insert("context", context)
+                    .insert("database", db),
+            ),
+            None => (
+                "CALL dbms.cluster.routing.getRoutingTable($context)",
+                Params::new().insert("context", context),
+            ),
+        };
+        let record = conn.run_single(cypher, params.build())?;
+        let missing = |key: &str| BoltError::MissingColumn {
+            name: key.to_string(),
+        };
+        let field = |key: &str| record.get(key).ok_or_else(|| missing(key));
+
+        let ttl = field("ttl")?.as_u64()?;
+        let mut table = RoutingTable {
+            ttl: Duration::from_secs(ttl),
+            ..RoutingTable::default()
+        };
+        let servers = field("servers")?;
+        servers.expect_type(ValueType::List)?;
+        for i in 0..servers.len() {
+            let server = servers.list_get(i).unwrap();
+            let role = server.get("role").ok_or_else(|| missing("role"))?;
+            let list = match String::try_from(&*role)?.as_str() {
+                "ROUTE" => &mut table.routers,
+                "READ" => &mut table.readers,
+                "WRITE" => &mut table.writers,
+                _ => continue,
+            };
+            if let Some(addresses) = server.get("addresses") {
+                addresses.expect_type(ValueType::List)?;
+                for j in 0..addresses.len() {
+                    list.push(String::try_from(&*addresses.list_get(j).unwrap())?);
+                }
+            }
+        }
+        Ok(table)
+    }
+}
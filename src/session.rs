@@ -0,0 +1,129 @@
+use crate::{
+    retry, AccessMode, BoltError, Connection, Connector, FromRecord, Record, Records,
+    RetryPolicy, Summary, Value,
+};
+
+/// A pooled connection checked out for a unit of work. A failed query resets the
+/// connection so the next one can run, and the connection goes back to the pool on drop.
+#[derive(Debug)]
+pub struct Session<'a> {
+    connector: &'a Connector,
+    mode: AccessMode,
+    conn: Connection<'a>,
+}
+
+impl<'a> Session<'a> {
+    pub fn new(connector: &'a Connector, mode: AccessMode) -> Result<Self, BoltError> {
+        Ok(Session {
+            connector,
+            mode,
+            conn: connector.acquire(mode)?,
+        })
+    }
+
+    pub fn run(&self, cypher: &str, params: Value) -> Result<ResultCursor<'_>, BoltError> {
+        self.conn.reset_if_failed()?;
+        match self.conn.run(cypher, params) {
+            Ok(records) => Ok(ResultCursor { records }),
+            Err(e) => {
+                let _ = self.conn.reset();
+                Err(e)
+            }
+        }
+    }
+
+    /// Runs the query to completion, retrying it on transient failures as `policy` allows.
+    /// The rows are buffered so that a failure part way through the stream can be retried
+    /// too. A connection-level failure swaps in a fresh connection from the pool before the
+    /// next attempt.
+    pub fn run_with_retry(
+        &mut self,
+        cypher: &str,
+        params: Value,
+        policy: &RetryPolicy,
+    ) -> Result<(Vec<Record>, Summary), BoltError> {
+        retry::retry(policy, || {
+            let res = self.run(cypher, params.clone()).and_then(|mut cursor| {
+                let records = cursor.by_ref().collect::<Result<Vec<_>, _>>()?;
+                Ok((records, cursor.consume()?))
+            });
+            if let Err(BoltError::Connection { .. }) = res {
+                self.conn = self.connector.acquire(self.mode)?;
+            }
+            res
+        })
+    }
+
+    pub fn connection(&self) -> &Connection<'a> {
+        &self.conn
+    }
+
+    pub fn close(self) -> Result<(), BoltError> {
+        self.conn.close()
+    }
+}
+
+#[derive(Debug)]
+pub struct ResultCursor<'s> {
+    records: Records<'s>,
+}
+
+impl<'s> ResultCursor<'s> {
+    pub fn fields(&self) -> &[String] {
+        self.records.fields()
+    }
+
+    /// Drains whatever rows have not been iterated yet. A failure already seen while
+    /// iterating is returned again here rather than a summary.
+    pub fn consume(self) -> Result<Summary, BoltError> {
+        self.records.consume()
+    }
+
+    /// Returns the only row of the result, erroring if there are none or more than one.
+    /// Any further rows are drained so the session stays usable.
+    pub fn single(mut self) -> Result<Record, BoltError> {
+        let record = match self.next() {
+            Some(record) => record?,
+            None => {
+                self.consume()?;
+                return Err(BoltError::NoRecords);
+            }
+        };
+        match self.next() {
+            None => {
+                self.consume()?;
+                Ok(record)
+            }
+            Some(other) => {
+                other?;
+                self.consume()?;
+                Err(BoltError::MultipleRecords)
+            }
+        }
+    }
+
+    /// Converts every row into a `T`, stopping at the first failure from either the server
+    /// or a conversion. The remaining rows are drained so the session stays usable.
+    pub fn collect_as<T: FromRecord>(mut self) -> Result<Vec<T>, BoltError> {
+        let mut rows = Vec::new();
+        while let Some(record) = self.next() {
+            match T::from_record(&record?) {
+                Ok(row) => rows.push(row),
+                Err(e) => {
+                    self.consume()?;
+                    return Err(e);
+                }
+            }
+        }
+        self.consume()?;
+        Ok(rows)
+    }
+}
+
+impl<'s> Iterator for ResultCursor<'s> {
+    type Item = Result<Record, BoltError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.records.next()
+    }
+}
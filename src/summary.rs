@@ -0,0 +1,289 @@
+use std::{collections::HashMap, convert::TryFrom, fmt, time::Duration};
+
+use crate::{Value, ValueType};
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Bookmark(String);
+
+impl Bookmark {
+    pub fn new(bookmark: String) -> Self {
+        Bookmark(bookmark)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Bookmark {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<Bookmark> for Value {
+    fn from(bookmark: Bookmark) -> Self {
+        Value::from_string(bookmark.0)
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum QueryType {
+    ReadOnly,
+    ReadWrite,
+    WriteOnly,
+    SchemaWrite,
+}
+
+#[derive(Debug, Clone)]
+pub struct Plan {
+    pub operator_type: String,
+    pub identifiers: Vec<String>,
+    pub arguments: HashMap<String, Value>,
+    pub children: Vec<Plan>,
+    /// Only present on a profiled plan.
+    pub db_hits: Option<i64>,
+    /// Only present on a profiled plan.
+    pub rows: Option<i64>,
+}
+
+impl Plan {
+    fn from_value(v: &Value) -> Self {
+        Plan {
+            operator_type: get_string(v, "operatorType").unwrap_or_default(),
+            identifiers: v
+                .get("identifiers")
+                .map(|l| {
+                    (0..l.len())
+                        .filter_map(|i| l.list_get(i))
+                        .filter(|s| s.get_type() == ValueType::String)
+                        .map(|s| s.as_string().to_string())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            arguments: v
+                .get("args")
+                .map(|args| {
+                    args.iter_dict()
+                        .map(|(k, v)| (k.to_string(), v.clone()))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            children: v
+                .get("children")
+                .map(|l| {
+                    (0..l.len())
+                        .filter_map(|i| l.list_get(i))
+                        .map(|c| Plan::from_value(&c))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            db_hits: get_integer(v, "dbHits"),
+            rows: get_integer(v, "rows"),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct InputPosition {
+    pub offset: i64,
+    pub line: i64,
+    pub column: i64,
+}
+
+impl InputPosition {
+    pub(crate) fn from_value(v: &Value) -> Self {
+        InputPosition {
+            offset: get_integer(v, "offset").unwrap_or(0),
+            line: get_integer(v, "line").unwrap_or(0),
+            column: get_integer(v, "column").unwrap_or(0),
+        }
+    }
+
+    // Syntax errors carry their position only in the message text, ending with e.g.
+    // `(line 1, column 8 (offset: 7))`.
+    pub(crate) fn from_message(message: &str) -> Option<Self> {
+        let start = message.rfind("(line ")?;
+        let mut numbers = message[start..]
+            .split(|c: char| !c.is_ascii_digit())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.parse().ok());
+        Some(InputPosition {
+            line: numbers.next()??,
+            column: numbers.next()??,
+            offset: numbers.next()??,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Notification {
+    pub code: String,
+    pub title: String,
+    pub description: String,
+    pub severity: String,
+    pub position: Option<InputPosition>,
+}
+
+impl Notification {
+    fn from_value(v: &Value) -> Self {
+        Notification {
+            code: get_string(v, "code").unwrap_or_default(),
+            title: get_string(v, "title").unwrap_or_default(),
+            description: get_string(v, "description").unwrap_or_default(),
+            severity: get_string(v, "severity").unwrap_or_default(),
+            position: v.get("position").map(|p| InputPosition::from_value(&p)),
+        }
+    }
+}
+
+/// Update statistics from the `stats` entry of the summary; counters the server left out
+/// are zero.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub struct SummaryCounters {
+    pub nodes_created: i64,
+    pub nodes_deleted: i64,
+    pub relationships_created: i64,
+    pub relationships_deleted: i64,
+    pub properties_set: i64,
+    pub labels_added: i64,
+    pub labels_removed: i64,
+    pub indexes_added: i64,
+    pub indexes_removed: i64,
+    pub constraints_added: i64,
+    pub constraints_removed: i64,
+    pub contains_updates: bool,
+}
+
+impl SummaryCounters {
+    fn from_value(v: &Value) -> Self {
+        let count = |key| get_integer(v, key).unwrap_or(0);
+        let mut counters = SummaryCounters {
+            nodes_created: count("nodes-created"),
+            nodes_deleted: count("nodes-deleted"),
+            relationships_created: count("relationships-created"),
+            relationships_deleted: count("relationships-deleted"),
+            properties_set: count("properties-set"),
+            labels_added: count("labels-added"),
+            labels_removed: count("labels-removed"),
+            indexes_added: count("indexes-added"),
+            indexes_removed: count("indexes-removed"),
+            constraints_added: count("constraints-added"),
+            constraints_removed: count("constraints-removed"),
+            contains_updates: false,
+        };
+        // Servers before 4.1 do not send the flag, so fall back to the counters.
+        counters.contains_updates = v
+            .get("contains-updates")
+            .filter(|b| b.get_type() == ValueType::Boolean)
+            .map(|b| b.as_boolean())
+            .unwrap_or(counters != SummaryCounters::default());
+        counters
+    }
+}
+
+#[derive(Debug)]
+pub struct Summary {
+    run_metadata: Value,
+    metadata: Value,
+}
+
+impl Summary {
+    pub(crate) fn new(run_metadata: Value, metadata: Value) -> Self {
+        Summary {
+            run_metadata,
+            metadata,
+        }
+    }
+
+    pub fn metadata(&self) -> &Value {
+        &self.metadata
+    }
+
+    /// `None` if the server did not report it, or reported a negative time.
+    pub fn result_available_after(&self) -> Option<Duration> {
+        get_integer(&self.run_metadata, "t_first")
+            .or_else(|| get_integer(&self.run_metadata, "result_available_after"))
+            .and_then(millis)
+    }
+
+    /// `None` if the server did not report it, or reported a negative time.
+    pub fn result_consumed_after(&self) -> Option<Duration> {
+        get_integer(&self.metadata, "t_last")
+            .or_else(|| get_integer(&self.metadata, "result_consumed_after"))
+            .and_then(millis)
+    }
+
+    pub fn query_type(&self) -> Option<QueryType> {
+        match get_string(&self.metadata, "type")?.as_str() {
+            "r" => Some(QueryType::ReadOnly),
+            "rw" => Some(QueryType::ReadWrite),
+            "w" => Some(QueryType::WriteOnly),
+            "s" => Some(QueryType::SchemaWrite),
+            _ => None,
+        }
+    }
+
+    pub fn bookmark(&self) -> Option<Bookmark> {
+        get_string(&self.metadata, "bookmark").map(Bookmark)
+    }
+
+    pub fn counters(&self) -> SummaryCounters {
+        self.metadata
+            .get("stats")
+            .map(|stats| SummaryCounters::from_value(&stats))
+            .unwrap_or_default()
+    }
+
+    pub fn notifications(&self) -> Vec<Notification> {
+        self.metadata
+            .get("notifications")
+            .map(|l| {
+                (0..l.len())
+                    .filter_map(|i| l.list_get(i))
+                    .map(|n| Notification::from_value(&n))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn plan(&self) -> Option<Plan> {
+        self.metadata.get("plan").map(|p| Plan::from_value(&p))
+    }
+
+    pub fn profile(&self) -> Option<Plan> {
+        self.metadata.get("profile").map(|p| Plan::from_value(&p))
+    }
+}
+
+fn get_integer(v: &Value, key: &str) -> Option<i64> {
+    v.get(key)
+        .filter(|v| v.get_type() == ValueType::Integer)
+        .map(|v| v.as_integer())
+}
+
+fn get_string(v: &Value, key: &str) -> Option<String> {
+    v.get(key)
+        .filter(|v| v.get_type() == ValueType::String)
+        .map(|v| v.as_string().to_string())
+}
+
+fn millis(ms: i64) -> Option<Duration> {
+    u64::try_from(ms).ok().map(Duration::from_millis)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value_map;
+
+    #[test]
+    fn negative_timings_are_none() {
+        let summary = Summary::new(value_map! { "t_first" => -1 }, value_map! { "t_last" => 5 });
+        assert_eq!(summary.result_available_after(), None);
+        assert_eq!(
+            summary.result_consumed_after(),
+            Some(Duration::from_millis(5))
+        );
+    }
+}
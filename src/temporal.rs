@@ -0,0 +1,193 @@
+use std::convert::TryFrom;
+
+#[cfg(feature = "chrono")]
+use chrono::{FixedOffset, NaiveTime, Timelike};
+
+use crate::{BoltError, StructureKind, Value};
+
+#[cfg(feature = "chrono")]
+const NANOS_PER_SECOND: i64 = 1_000_000_000;
+
+/// A time of day without a time zone, as nanoseconds since midnight.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct LocalTime {
+    pub nanoseconds: i64,
+}
+
+/// A time of day with a fixed offset from UTC in seconds.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Time {
+    pub nanoseconds: i64,
+    pub tz_offset_seconds: i64,
+}
+
+impl Value {
+    pub fn from_local_time(t: LocalTime) -> Self {
+        Value::new().into_structure(
+            StructureKind::LocalTime.code(),
+            vec![Value::from_integer(t.nanoseconds)],
+        )
+    }
+
+    pub fn as_local_time(&self) -> Result<LocalTime, BoltError> {
+        self.expect_structure(StructureKind::LocalTime, 1)?;
+        Ok(LocalTime {
+            nanoseconds: i64::try_from(&*self.structure_field(0).unwrap())?,
+        })
+    }
+
+    pub fn from_time(t: Time) -> Self {
+        Value::new().into_structure(
+            StructureKind::Time.code(),
+            vec![
+                Value::from_integer(t.nanoseconds),
+                Value::from_integer(t.tz_offset_seconds),
+            ],
+        )
+    }
+
+    pub fn as_time(&self) -> Result<Time, BoltError> {
+        self.expect_structure(StructureKind::Time, 2)?;
+        Ok(Time {
+            nanoseconds: i64::try_from(&*self.structure_field(0).unwrap())?,
+            tz_offset_seconds: i64::try_from(&*self.structure_field(1).unwrap())?,
+        })
+    }
+}
+
+impl From<LocalTime> for Value {
+    fn from(t: LocalTime) -> Self {
+        Value::from_local_time(t)
+    }
+}
+
+impl From<Time> for Value {
+    fn from(t: Time) -> Self {
+        Value::from_time(t)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<NaiveTime> for LocalTime {
+    fn from(t: NaiveTime) -> Self {
+        LocalTime {
+            nanoseconds: i64::from(t.num_seconds_from_midnight()) * NANOS_PER_SECOND
+                + i64::from(t.nanosecond()),
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<LocalTime> for NaiveTime {
+    type Error = BoltError;
+
+    fn try_from(t: LocalTime) -> Result<Self, BoltError> {
+        let out_of_range = BoltError::IntegerOutOfRange {
+            value: t.nanoseconds.into(),
+            target: "NaiveTime",
+        };
+        if t.nanoseconds < 0 {
+            return Err(out_of_range);
+        }
+        let secs =
+            u32::try_from(t.nanoseconds / NANOS_PER_SECOND).map_err(|_| out_of_range.clone())?;
+        let nanos = (t.nanoseconds % NANOS_PER_SECOND) as u32;
+        NaiveTime::from_num_seconds_from_midnight_opt(secs, nanos).ok_or(out_of_range)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<(NaiveTime, FixedOffset)> for Time {
+    fn from((t, offset): (NaiveTime, FixedOffset)) -> Self {
+        Time {
+            nanoseconds: LocalTime::from(t).nanoseconds,
+            tz_offset_seconds: offset.local_minus_utc().into(),
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<Time> for (NaiveTime, FixedOffset) {
+    type Error = BoltError;
+
+    fn try_from(t: Time) -> Result<Self, BoltError> {
+        let time = NaiveTime::try_from(LocalTime {
+            nanoseconds: t.nanoseconds,
+        })?;
+        let offset = i32::try_from(t.tz_offset_seconds)
+            .ok()
+            .and_then(FixedOffset::east_opt)
+            .ok_or(BoltError::IntegerOutOfRange {
+                value: t.tz_offset_seconds.into(),
+                target: "FixedOffset",
+            })?;
+        Ok((time, offset))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIDNIGHT: i64 = 0;
+    const LAST_NANOSECOND: i64 = 86_399_999_999_999;
+
+    #[test]
+    fn local_time_round_trip() {
+        for &nanoseconds in &[MIDNIGHT, LAST_NANOSECOND] {
+            let t = LocalTime { nanoseconds };
+            assert_eq!(Value::from(t).as_local_time().unwrap(), t);
+        }
+    }
+
+    #[test]
+    fn time_round_trip() {
+        for &nanoseconds in &[MIDNIGHT, LAST_NANOSECOND] {
+            for &tz_offset_seconds in &[-18 * 3600, 0, 18 * 3600] {
+                let t = Time {
+                    nanoseconds,
+                    tz_offset_seconds,
+                };
+                assert_eq!(Value::from(t).as_time().unwrap(), t);
+            }
+        }
+    }
+
+    #[test]
+    fn arity_is_checked() {
+        let v = Value::new().into_structure(StructureKind::Time.code(), vec![Value::from(0)]);
+        assert!(matches!(
+            v.as_time(),
+            Err(BoltError::StructureArity {
+                expected: 2,
+                found: 1,
+                ..
+            })
+        ));
+        assert!(matches!(
+            Value::from(LocalTime { nanoseconds: 0 }).as_time(),
+            Err(BoltError::UnexpectedStructure { .. })
+        ));
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_boundaries() {
+        let midnight = NaiveTime::from_hms_nano_opt(0, 0, 0, 0).unwrap();
+        let last = NaiveTime::from_hms_nano_opt(23, 59, 59, 999_999_999).unwrap();
+        for &(t, nanoseconds) in &[(midnight, MIDNIGHT), (last, LAST_NANOSECOND)] {
+            assert_eq!(LocalTime::from(t), LocalTime { nanoseconds });
+            assert_eq!(NaiveTime::try_from(LocalTime { nanoseconds }).unwrap(), t);
+
+            let offset = FixedOffset::east_opt(3600).unwrap();
+            let time = Time::from((t, offset));
+            assert_eq!(
+                <(NaiveTime, FixedOffset)>::try_from(time).unwrap(),
+                (t, offset)
+            );
+        }
+        for &nanoseconds in &[-1, LAST_NANOSECOND + 1] {
+            assert!(NaiveTime::try_from(LocalTime { nanoseconds }).is_err());
+        }
+    }
+}
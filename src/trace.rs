@@ -0,0 +1,112 @@
+#[cfg(feature = "tracing")]
+use std::time::Instant;
+
+#[cfg(feature = "tracing")]
+use tracing::field;
+
+use crate::{BoltError, Connector, Value};
+#[cfg(feature = "tracing")]
+use crate::{diff::describe, ValueType};
+
+/// What goes into the `bolt.query` span opened for each query, see `Bolt::set_tracing`.
+/// By default the Cypher text and the number of parameters are recorded but the parameter
+/// values are not, as they are the likeliest place for personal data to turn up.
+#[cfg(feature = "tracing")]
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub struct TraceOptions {
+    redact_cypher: bool,
+    record_params: bool,
+}
+
+#[cfg(feature = "tracing")]
+impl TraceOptions {
+    pub fn new() -> Self {
+        TraceOptions::default()
+    }
+
+    /// Leaves the Cypher text out of the span, e.g. when queries embed literals.
+    pub fn redact_cypher(mut self) -> Self {
+        self.redact_cypher = true;
+        self
+    }
+
+    /// Records each parameter's name and value in the span.
+    pub fn record_params(mut self) -> Self {
+        self.record_params = true;
+        self
+    }
+}
+
+/// The span for a single query, carried from RUN until its result stream is finished. It is
+/// an empty placeholder without the `tracing` feature.
+#[derive(Debug)]
+pub(crate) struct QueryTrace {
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+    #[cfg(feature = "tracing")]
+    started: Instant,
+    #[cfg(feature = "tracing")]
+    rows: u64,
+}
+
+impl QueryTrace {
+    #[cfg(feature = "tracing")]
+    pub(crate) fn start(connector: &Connector, cypher: &str, params: &Value) -> Self {
+        let options = connector.trace_options;
+        let span = tracing::info_span!(
+            "bolt.query",
+            cypher = field::Empty,
+            params = params.len(),
+            params.values = field::Empty,
+            rows = field::Empty,
+            elapsed_ms = field::Empty,
+            error = field::Empty,
+        );
+        if !options.redact_cypher {
+            span.record("cypher", cypher);
+        }
+        if options.record_params && params.get_type() == ValueType::Dictionary {
+            let values = params
+                .iter_dict()
+                .map(|(k, v)| format!("{}={}", k, describe(&v)))
+                .collect::<Vec<_>>();
+            span.record("params.values", values.join(", ").as_str());
+        }
+        QueryTrace {
+            span,
+            started: Instant::now(),
+            rows: 0,
+        }
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    pub(crate) fn start(_connector: &Connector, _cypher: &str, _params: &Value) -> Self {
+        QueryTrace {}
+    }
+
+    /// Runs `f`, e.g. a `fetch_summary`, inside the query's span.
+    pub(crate) fn in_scope<R>(&self, f: impl FnOnce() -> R) -> R {
+        #[cfg(feature = "tracing")]
+        let _entered = self.span.enter();
+        f()
+    }
+
+    pub(crate) fn row(&mut self) {
+        #[cfg(feature = "tracing")]
+        {
+            self.rows += 1;
+        }
+    }
+
+    pub(crate) fn finish(&self, _failure: Option<&BoltError>) {
+        #[cfg(feature = "tracing")]
+        {
+            self.span.record("rows", self.rows);
+            let elapsed = self.started.elapsed().as_millis() as u64;
+            self.span.record("elapsed_ms", elapsed);
+            if let Some(e) = _failure {
+                self.span.record("error", e.to_string().as_str());
+            }
+        }
+    }
+}
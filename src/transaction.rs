@@ -0,0 +1,47 @@
+use crate::{BoltError, Connection, Records, Value};
+
+#[derive(Debug)]
+pub struct Tx<'c> {
+    conn: &'c Connection<'c>,
+}
+
+impl<'c> Tx<'c> {
+    pub fn run(&self, cypher: &str, params: Value) -> Result<Records<'_>, BoltError> {
+        self.conn.run(cypher, params)
+    }
+}
+
+// Rolls the transaction back unless disarmed, including while unwinding from a panic. If
+// the rollback itself is refused (the connection has already failed) a reset is sent
+// instead, which also discards the open transaction.
+struct RollbackGuard<'c> {
+    conn: &'c Connection<'c>,
+    armed: bool,
+}
+
+impl<'c> Drop for RollbackGuard<'c> {
+    fn drop(&mut self) {
+        if self.armed && self.conn.rollback().is_err() {
+            let _ = self.conn.reset();
+        }
+    }
+}
+
+impl<'a> Connection<'a> {
+    /// Runs `f` inside a transaction, committing when it returns `Ok` and rolling back
+    /// when it returns `Err` or panics.
+    pub fn with_transaction<F, T>(&self, f: F) -> Result<T, BoltError>
+    where
+        F: FnOnce(&Tx) -> Result<T, BoltError>,
+    {
+        self.begin(None)?;
+        let mut guard = RollbackGuard {
+            conn: self,
+            armed: true,
+        };
+        let res = f(&Tx { conn: self })?;
+        self.commit()?;
+        guard.armed = false;
+        Ok(res)
+    }
+}
@@ -1,7 +1,22 @@
 use std::{
-    collections::HashMap,
-    ffi::{CStr, CString},
-    slice,
+    borrow::Cow,
+    cmp::Ordering,
+    collections::{hash_map::DefaultHasher, HashMap},
+    convert::TryFrom,
+    hash::{Hash, Hasher},
+    io::{self, Read},
+    iter::FromIterator,
+    mem,
+    ops::Deref,
+    os::raw::c_char,
+    ptr::{self, NonNull},
+    slice, str,
+    sync::Arc,
+};
+
+use crate::{
+    config::{NTTWrap, NTTWrapper},
+    BoltError,
 };
 
 make_enum!(ValueType,
@@ -16,27 +31,154 @@ make_enum!(ValueType,
     Structure => seabolt_sys::BoltType::BOLT_STRUCTURE,
 );
 
-#[derive(Debug)]
+impl ValueType {
+    /// Translates a raw `seabolt_sys::BoltType` index, for code that also calls seabolt
+    /// directly. An index this crate does not know becomes `Unknown`.
+    pub fn from_bolt_type(t: u32) -> Self {
+        ValueType::from_idx(t)
+    }
+
+    /// Fails with `BoltError::UnknownVariant` for `Unknown`, which has no index of its own.
+    pub fn to_bolt_type(&self) -> Result<u32, BoltError> {
+        self.as_idx()
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum StructureKind {
+    Node,
+    Relationship,
+    UnboundRelationship,
+    Path,
+    Date,
+    Time,
+    LocalTime,
+    DateTime,
+    DateTimeZoneId,
+    LocalDateTime,
+    Duration,
+    Point2D,
+    Point3D,
+    Unknown(i16),
+}
+
+impl StructureKind {
+    pub fn from_code(code: i16) -> Self {
+        match u8::try_from(code) {
+            Ok(b'N') => StructureKind::Node,
+            Ok(b'R') => StructureKind::Relationship,
+            Ok(b'r') => StructureKind::UnboundRelationship,
+            Ok(b'P') => StructureKind::Path,
+            Ok(b'D') => StructureKind::Date,
+            Ok(b'T') => StructureKind::Time,
+            Ok(b't') => StructureKind::LocalTime,
+            Ok(b'F') => StructureKind::DateTime,
+            Ok(b'f') => StructureKind::DateTimeZoneId,
+            Ok(b'd') => StructureKind::LocalDateTime,
+            Ok(b'E') => StructureKind::Duration,
+            Ok(b'X') => StructureKind::Point2D,
+            Ok(b'Y') => StructureKind::Point3D,
+            _ => StructureKind::Unknown(code),
+        }
+    }
+
+    pub fn code(&self) -> i16 {
+        let c = match self {
+            StructureKind::Node => b'N',
+            StructureKind::Relationship => b'R',
+            StructureKind::UnboundRelationship => b'r',
+            StructureKind::Path => b'P',
+            StructureKind::Date => b'D',
+            StructureKind::Time => b'T',
+            StructureKind::LocalTime => b't',
+            StructureKind::DateTime => b'F',
+            StructureKind::DateTimeZoneId => b'f',
+            StructureKind::LocalDateTime => b'd',
+            StructureKind::Duration => b'E',
+            StructureKind::Point2D => b'X',
+            StructureKind::Point3D => b'Y',
+            StructureKind::Unknown(code) => return *code,
+        };
+        c as i16
+    }
+}
+
+/// A structure decoded by `Value::as_structure`. Its fields are copies, so it owns them
+/// independently of the value it came from.
+#[derive(Debug, Eq, PartialEq)]
 pub struct Structure {
     pub code: i16,
     pub fields: Vec<Value>,
 }
 
+impl Structure {
+    pub fn kind(&self) -> StructureKind {
+        StructureKind::from_code(self.code)
+    }
+}
+
+/// An owned seabolt value. The `from_*` constructors, the `From` impls and `clone` panic if
+/// seabolt cannot allocate the value, the same way a failed Rust allocation aborts. To handle
+/// that instead, start from `try_new` and use the matching `into_*` setter.
+#[repr(transparent)]
 #[derive(Debug)]
 pub struct Value {
     ptr: *mut seabolt_sys::BoltValue,
 }
 
+pub type ValueRef<'a> = NTTWrapper<'a, Value>;
+
 impl Value {
+    /// Panics if seabolt fails to allocate the value. Every `from_*` constructor goes through
+    /// here.
     pub(crate) fn new() -> Self {
+        Value::try_new().expect("BoltValue_create failed to allocate")
+    }
+
+    /// Allocates a null value, returning `None` instead of panicking when seabolt is out of
+    /// memory.
+    pub fn try_new() -> Option<Value> {
         let ptr = unsafe { seabolt_sys::BoltValue_create() };
-        Value { ptr }
+        if ptr.is_null() {
+            None
+        } else {
+            Some(Value { ptr })
+        }
     }
 
-    pub(crate) unsafe fn from_ptr(p: *mut seabolt_sys::BoltValue) -> Self {
+    /// Takes ownership of a seabolt value, which is destroyed when the `Value` drops.
+    ///
+    /// # Safety
+    ///
+    /// `p` must be a valid, non-null value from `BoltValue_create` (or `into_raw`) that
+    /// nothing else will destroy.
+    pub unsafe fn from_ptr(p: *mut seabolt_sys::BoltValue) -> Self {
         Value { ptr: p }
     }
 
+    /// Copies a seabolt value into a new owned `Value`, or `None` if seabolt is out of
+    /// memory.
+    ///
+    /// # Safety
+    ///
+    /// `p` must point to a valid value.
+    pub(crate) unsafe fn duplicate(p: *const seabolt_sys::BoltValue) -> Option<Value> {
+        let ptr = seabolt_sys::BoltValue_duplicate(p);
+        if ptr.is_null() {
+            None
+        } else {
+            Some(Value { ptr })
+        }
+    }
+
+    /// Gives up ownership of the underlying seabolt value without destroying it. The caller
+    /// becomes responsible for passing it to `BoltValue_destroy`, or back to `from_ptr`.
+    pub fn into_raw(self) -> *mut seabolt_sys::BoltValue {
+        let ptr = self.ptr;
+        mem::forget(self);
+        ptr
+    }
+
     pub(crate) fn as_ptr(&self) -> *mut seabolt_sys::BoltValue {
         self.ptr
     }
@@ -45,6 +187,93 @@ impl Value {
         ValueType::from_idx(unsafe { seabolt_sys::BoltValue_type(self.ptr) })
     }
 
+    pub fn is_null(&self) -> bool {
+        self.get_type() == ValueType::Null
+    }
+
+    pub fn is_boolean(&self) -> bool {
+        self.get_type() == ValueType::Boolean
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.get_type() == ValueType::Integer
+    }
+
+    pub fn is_float(&self) -> bool {
+        self.get_type() == ValueType::Float
+    }
+
+    pub fn is_string(&self) -> bool {
+        self.get_type() == ValueType::String
+    }
+
+    pub fn is_dict(&self) -> bool {
+        self.get_type() == ValueType::Dictionary
+    }
+
+    pub fn is_list(&self) -> bool {
+        self.get_type() == ValueType::List
+    }
+
+    pub fn is_bytes(&self) -> bool {
+        self.get_type() == ValueType::Bytes
+    }
+
+    pub fn is_structure(&self) -> bool {
+        self.get_type() == ValueType::Structure
+    }
+
+    pub fn is_number(&self) -> bool {
+        self.is_integer() || self.is_float()
+    }
+
+    /// A rough estimate of the memory held by this value and everything nested in it,
+    /// counting a fixed header per value plus string and byte payloads.
+    pub fn deep_size_bytes(&self) -> usize {
+        // Approximate size of seabolt's BoltValue header (type, size and inline data).
+        const HEADER: usize = 32;
+        HEADER
+            + match self.get_type() {
+                ValueType::String | ValueType::Bytes => self.len(),
+                ValueType::List => (0..self.len())
+                    .filter_map(|i| self.list_get(i))
+                    .map(|v| v.deep_size_bytes())
+                    .sum(),
+                ValueType::Dictionary => self
+                    .iter_dict()
+                    .map(|(k, v)| HEADER + k.len() + v.deep_size_bytes())
+                    .sum(),
+                ValueType::Structure => (0..self.len())
+                    .filter_map(|i| self.structure_field(i))
+                    .map(|v| v.deep_size_bytes())
+                    .sum(),
+                _ => 0,
+            }
+    }
+
+    pub(crate) fn expect_type(&self, expected: ValueType) -> Result<(), BoltError> {
+        let found = self.get_type();
+        if found == expected {
+            Ok(())
+        } else {
+            Err(BoltError::TypeMismatch { expected, found })
+        }
+    }
+
+    /// Number of entries for lists, dictionaries and structures, and number of bytes for
+    /// strings and bytes. Null is always 0 and the other scalars are always 1.
+    pub fn len(&self) -> usize {
+        match self.get_type() {
+            ValueType::Null => 0,
+            ValueType::Boolean | ValueType::Integer | ValueType::Float => 1,
+            _ => unsafe { seabolt_sys::BoltValue_size(self.ptr) as usize },
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
     // Null
     pub fn null(&mut self) {
         unsafe {
@@ -89,11 +318,43 @@ impl Value {
         unsafe { seabolt_sys::BoltInteger_get(self.ptr) }
     }
 
+    /// Takes any type that widens losslessly to `i64`: `i8`, `i16`, `i32`, `i64`, `u8`, `u16`
+    /// and `u32`. `u64` and `usize` can overflow, see `try_from_u64` and `try_from_usize`.
+    /// The named constructors below pin the type down for integer literals.
     pub fn from_integer<T: Into<i64>>(v: T) -> Self {
         Value::new().into_integer(v)
     }
 
+    pub fn from_i64(v: i64) -> Self {
+        Value::from_integer(v)
+    }
+
+    pub fn from_i32(v: i32) -> Self {
+        Value::from_integer(v)
+    }
+
+    pub fn from_u32(v: u32) -> Self {
+        Value::from_integer(v)
+    }
+
+    /// Bolt integers are signed 64-bit, so values above `i64::MAX` are rejected rather than
+    /// wrapped. Fails with `BoltError::OutOfMemory` if seabolt cannot allocate the value.
+    pub fn try_from_u64(v: u64) -> Result<Self, BoltError> {
+        let v = i64::try_from(v).map_err(|_| BoltError::IntegerOutOfRange {
+            value: v.into(),
+            target: "i64",
+        })?;
+        let value = Value::try_new().ok_or(BoltError::OutOfMemory)?;
+        Ok(value.into_integer(v))
+    }
+
+    pub fn try_from_usize(v: usize) -> Result<Self, BoltError> {
+        Value::try_from_u64(v as u64)
+    }
+
     // Float
+    /// PackStream encodes floats as raw 64-bit IEEE-754, so NaN (including its payload),
+    /// the infinities and -0.0 all survive the round trip to the server unchanged.
     pub fn into_float<T: Into<f64>>(self, v: T) -> Self {
         unsafe {
             seabolt_sys::BoltValue_format_as_Float(self.ptr, v.into());
@@ -106,102 +367,292 @@ impl Value {
         unsafe { seabolt_sys::BoltFloat_get(self.ptr) }
     }
 
+    /// Reads either numeric type as a float. Integers beyond 2^53 in magnitude cannot be
+    /// represented exactly and are rounded to the nearest `f64`.
+    pub fn as_f64_lossy(&self) -> Result<f64, BoltError> {
+        f64::try_from(self)
+    }
+
     pub fn from_float<T: Into<f64>>(v: T) -> Self {
         Value::new().into_float(v)
     }
 
     // String
     pub fn into_string<T: ToString>(self, v: T) -> Self {
-        let s = CString::new(v.to_string()).unwrap();
+        self.into_raw_string(v.to_string().as_bytes())
+    }
+
+    /// Writes `bytes` as the string's contents unchanged, whether or not they are UTF-8.
+    /// The server still interprets every string as UTF-8, so this is only for edge cases
+    /// such as round-tripping a Latin-1 column; read such values back with `as_str_bytes`.
+    pub fn into_raw_string(self, bytes: &[u8]) -> Self {
         unsafe {
             seabolt_sys::BoltValue_format_as_String(
                 self.ptr,
-                s.as_ptr(),
-                s.to_bytes_with_nul().len() as i32,
+                bytes.as_ptr() as *const c_char,
+                bolt_len(bytes.len()),
             );
         }
         self
     }
 
+    /// Panics if `bytes` is longer than `i32::MAX`.
+    pub fn from_raw_string(bytes: &[u8]) -> Self {
+        Value::new().into_raw_string(bytes)
+    }
+
     pub fn as_string(&self) -> &str {
+        str::from_utf8(self.as_str_bytes()).unwrap()
+    }
+
+    /// The exact bytes of the string. Bolt strings are length-prefixed rather than NUL
+    /// terminated, so they may contain NUL bytes.
+    pub fn as_str_bytes(&self) -> &[u8] {
         assert_eq!(self.get_type(), ValueType::String);
         unsafe {
-            CStr::from_ptr(seabolt_sys::BoltString_get(self.ptr))
-                .to_str()
-                .unwrap()
+            raw_slice(
+                seabolt_sys::BoltString_get(self.ptr),
+                seabolt_sys::BoltValue_size(self.ptr),
+            )
         }
     }
 
+    pub fn as_str_lossy(&self) -> Cow<'_, str> {
+        String::from_utf8_lossy(self.as_str_bytes())
+    }
+
+    /// Panics if the string is longer than `i32::MAX` bytes.
     pub fn from_string<T: ToString>(v: T) -> Self {
         Value::new().into_string(v)
     }
 
     // Dict
     pub fn into_dict<T: IntoIterator<Item = (String, Value)>>(self, v: T) -> Self {
+        self.try_into_dict(v).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// `into_dict`, but returning `BoltError::OutOfMemory` instead of panicking when seabolt
+    /// cannot allocate room for an entry, or `BoltError::IntegerOutOfRange` when there are
+    /// more entries or a longer key than seabolt can size.
+    pub fn try_into_dict<T: IntoIterator<Item = (String, Value)>>(
+        self,
+        v: T,
+    ) -> Result<Self, BoltError> {
         let dict = v.into_iter().collect::<HashMap<_, _>>();
         unsafe {
-            seabolt_sys::BoltValue_format_as_Dictionary(self.ptr, dict.len() as i32);
+            seabolt_sys::BoltValue_format_as_Dictionary(self.ptr, try_bolt_len(dict.len())?);
         }
         for (i, (k, v)) in dict.into_iter().enumerate() {
-            let s = CString::new(k).unwrap();
-            unsafe {
-                seabolt_sys::BoltDictionary_set_key(
-                    self.ptr,
-                    i as i32,
-                    s.as_ptr(),
-                    s.as_bytes_with_nul().len() as i32,
-                );
+            self.set_entry(i, &k, &v)?;
+        }
+        Ok(self)
+    }
+
+    fn set_entry(&self, i: usize, key: &str, v: &Value) -> Result<(), BoltError> {
+        let key_len = try_bolt_len(key.len())?;
+        unsafe {
+            seabolt_sys::BoltDictionary_set_key(
+                self.ptr,
+                i as i32,
+                key.as_ptr() as *const c_char,
+                key_len,
+            );
+        }
+        let p = unsafe { seabolt_sys::BoltDictionary_value(self.ptr, i as i32) };
+        v.copy_to(p)
+    }
+
+    fn dict_index(&self, key: &str) -> Option<usize> {
+        self.iter_dict().position(|(k, _)| k == key)
+    }
+
+    // Reformats as a dictionary with room for `extra` more entries than `old` has (less the
+    // skipped one) and copies the rest of `old` across, keeping their order.
+    fn relayout_dict(
+        &mut self,
+        old: &Value,
+        skip: Option<usize>,
+        extra: usize,
+    ) -> Result<(), BoltError> {
+        let n = old.len() - skip.map_or(0, |_| 1) + extra;
+        let n = try_bolt_len(n)?;
+        unsafe { seabolt_sys::BoltValue_format_as_Dictionary(self.ptr, n) };
+        let kept = old
+            .iter_dict()
+            .enumerate()
+            .filter(|(i, _)| Some(*i) != skip);
+        for (j, (_, (k, v))) in kept.enumerate() {
+            self.set_entry(j, k, &v)?;
+        }
+        Ok(())
+    }
+
+    /// Replaces the value under `key`, or appends a new entry. Appending copies the existing
+    /// entries into a reformatted dictionary, as seabolt cannot grow one in place.
+    pub fn dict_set(&mut self, key: &str, v: Value) -> Result<(), BoltError> {
+        self.expect_type(ValueType::Dictionary)?;
+        match self.dict_index(key) {
+            Some(i) => {
+                let p = unsafe { seabolt_sys::BoltDictionary_value(self.ptr, i as i32) };
+                v.copy_to(p)
+            }
+            None => {
+                let old = self.clone();
+                self.relayout_dict(&old, None, 1)?;
+                self.set_entry(old.len(), key, &v)
             }
-            let p = unsafe { seabolt_sys::BoltDictionary_value(self.ptr, i as i32) };
-            unsafe { seabolt_sys::BoltValue_copy(v.ptr, p) };
         }
-        self
     }
 
+    /// Returns whether `key` was present.
+    pub fn dict_remove(&mut self, key: &str) -> Result<bool, BoltError> {
+        self.expect_type(ValueType::Dictionary)?;
+        match self.dict_index(key) {
+            Some(i) => {
+                let old = self.clone();
+                self.relayout_dict(&old, Some(i), 0)?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Deep-copies every entry, as the values inside the dictionary stay owned by `self`.
     pub fn as_dict(&self) -> HashMap<String, Value> {
         assert_eq!(self.get_type(), ValueType::Dictionary);
+        self.iter_dict()
+            .map(|(k, v)| (k.to_string(), v.clone()))
+            .collect()
+    }
+
+    /// Like `as_dict`, but the keys and values borrow from `self` instead of being copied.
+    pub fn as_dict_ref(&self) -> HashMap<&str, ValueRef<'_>> {
+        assert_eq!(self.get_type(), ValueType::Dictionary);
+        self.iter_dict().collect()
+    }
+
+    pub fn get(&self, key: &str) -> Option<ValueRef<'_>> {
+        if self.get_type() != ValueType::Dictionary {
+            return None;
+        }
         let size = unsafe { seabolt_sys::BoltValue_size(self.ptr) };
-        let mut dict: HashMap<String, Value> = HashMap::with_capacity(size as usize);
         for i in 0..size {
-            let k = unsafe {
-                CStr::from_ptr(seabolt_sys::BoltDictionary_get_key(self.ptr, i))
-                    .to_str()
-                    .unwrap()
-            };
-            let v = unsafe { Value::from_ptr(seabolt_sys::BoltDictionary_value(self.ptr, i)) };
-            dict.insert(k.to_string(), v);
+            if self.key_bytes(i) == key.as_bytes() {
+                let p = unsafe { seabolt_sys::BoltDictionary_value(self.ptr, i) };
+                return NonNull::new(p).map(NTTWrapper::new);
+            }
+        }
+        None
+    }
+
+    /// Follows `path` through nested dictionaries, `None` if a key is missing or a value
+    /// along the way is not a dictionary. An empty path gives back the value itself.
+    pub fn get_path(&self, path: &[&str]) -> Option<ValueRef<'_>> {
+        let mut p = NonNull::new(self.ptr)?;
+        for key in path {
+            let v: ValueRef<'_> = NTTWrapper::new(p);
+            p = NonNull::new(v.get(key)?.ptr)?;
+        }
+        Some(NTTWrapper::new(p))
+    }
+
+    /// `false` for anything but a dictionary.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.get_type() == ValueType::Dictionary
+            && (0..self.len() as i32).any(|i| self.key_bytes(i) == key.as_bytes())
+    }
+
+    pub fn iter_dict(&self) -> impl Iterator<Item = (&str, ValueRef<'_>)> + '_ {
+        let size = if self.get_type() == ValueType::Dictionary {
+            unsafe { seabolt_sys::BoltValue_size(self.ptr) }
+        } else {
+            0
+        };
+        (0..size).map(move |i| unsafe {
+            let k = str::from_utf8(self.key_bytes(i)).unwrap();
+            let p = seabolt_sys::BoltDictionary_value(self.ptr, i);
+            (k, NTTWrapper::new(NonNull::new_unchecked(p)))
+        })
+    }
+
+    // Keys are length-prefixed strings too, so they are read by size rather than up to a NUL.
+    pub(crate) fn key_bytes(&self, i: i32) -> &[u8] {
+        unsafe {
+            raw_slice(
+                seabolt_sys::BoltDictionary_get_key(self.ptr, i),
+                seabolt_sys::BoltDictionary_get_key_size(self.ptr, i),
+            )
         }
-        dict
     }
 
+    /// Panics where `try_into_dict` would fail.
     pub fn from_dict<T: IntoIterator<Item = (String, Value)>>(v: T) -> Self {
         Value::new().into_dict(v)
     }
 
     // List
     pub fn into_list<T: IntoIterator<Item = Value>>(self, v: T) -> Self {
+        self.try_into_list(v).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// `into_list`, but returning `BoltError::OutOfMemory` instead of panicking when seabolt
+    /// cannot allocate room for an element, or `BoltError::IntegerOutOfRange` when there are
+    /// more elements than seabolt can size.
+    pub fn try_into_list<T: IntoIterator<Item = Value>>(self, v: T) -> Result<Self, BoltError> {
         let vec = v.into_iter().collect::<Vec<_>>();
         unsafe {
-            seabolt_sys::BoltValue_format_as_List(self.ptr, vec.len() as i32);
+            seabolt_sys::BoltValue_format_as_List(self.ptr, try_bolt_len(vec.len())?);
         }
         for (i, v) in vec.into_iter().enumerate() {
             let p = unsafe { seabolt_sys::BoltList_value(self.ptr, i as i32) };
-            unsafe { seabolt_sys::BoltValue_copy(v.ptr, p) };
+            v.copy_to(p)?;
         }
-        self
+        Ok(self)
     }
 
+    /// Deep-copies every element, as the values inside the list stay owned by `self`.
     pub fn as_list(&self) -> Vec<Value> {
         assert_eq!(self.get_type(), ValueType::List);
-        let size = unsafe { seabolt_sys::BoltValue_size(self.ptr) };
-        let mut vec: Vec<Value> = Vec::with_capacity(size as usize);
-        for i in 0..size {
-            let v = unsafe { Value::from_ptr(seabolt_sys::BoltList_value(self.ptr, i)) };
-            vec.push(v);
+        (0..self.len())
+            .map(|i| self.list_get(i).unwrap().clone())
+            .collect()
+    }
+
+    pub fn list_get(&self, i: usize) -> Option<ValueRef<'_>> {
+        if self.get_type() != ValueType::List || i >= self.len() {
+            return None;
+        }
+        let p = unsafe { seabolt_sys::BoltList_value(self.ptr, i as i32) };
+        NonNull::new(p).map(NTTWrapper::new)
+    }
+
+    /// Appends to the list. seabolt cannot grow a list in place, so the existing elements
+    /// are copied into a reformatted one, making each push O(n).
+    pub fn list_push(&mut self, v: Value) -> Result<(), BoltError> {
+        self.expect_type(ValueType::List)?;
+        let old = self.clone();
+        let n = old.len();
+        let len = try_bolt_len(n + 1)?;
+        unsafe { seabolt_sys::BoltValue_format_as_List(self.ptr, len) };
+        for i in 0..n {
+            let p = unsafe { seabolt_sys::BoltList_value(self.ptr, i as i32) };
+            old.list_get(i).unwrap().copy_to(p)?;
         }
-        vec
+        let p = unsafe { seabolt_sys::BoltList_value(self.ptr, n as i32) };
+        v.copy_to(p)
     }
 
+    pub fn list_set(&mut self, i: usize, v: Value) -> Result<(), BoltError> {
+        self.expect_type(ValueType::List)?;
+        let len = self.len();
+        if i >= len {
+            return Err(BoltError::IndexOutOfBounds { index: i, len });
+        }
+        let p = unsafe { seabolt_sys::BoltList_value(self.ptr, i as i32) };
+        v.copy_to(p)
+    }
+
+    /// Panics where `try_into_list` would fail.
     pub fn from_list<T: IntoIterator<Item = Value>>(v: T) -> Self {
         Value::new().into_list(v)
     }
@@ -212,7 +663,7 @@ impl Value {
             seabolt_sys::BoltValue_format_as_Bytes(
                 self.ptr,
                 v.as_mut_ptr() as *mut i8,
-                v.len() as i32,
+                bolt_len(v.len()),
             );
         }
         self
@@ -225,37 +676,450 @@ impl Value {
         unsafe { slice::from_raw_parts(seabolt_sys::BoltBytes_get_all(self.ptr) as *mut u8, size) }
     }
 
+    pub fn as_bytes_owned(&self) -> Vec<u8> {
+        self.as_bytes().to_vec()
+    }
+
+    pub fn try_as_bytes_owned(&self) -> Result<Vec<u8>, BoltError> {
+        self.expect_type(ValueType::Bytes)?;
+        Ok(self.as_bytes_owned())
+    }
+
+    /// Panics if `v` is longer than `i32::MAX`; see `from_reader` for a length check that
+    /// fails instead.
     pub fn from_bytes(v: &mut [u8]) -> Self {
         Value::new().into_bytes(v)
     }
 
+    /// Reads exactly `len` bytes from `r` into a bytes value, failing with
+    /// `io::ErrorKind::UnexpectedEof` if the reader runs out first, or with
+    /// `io::ErrorKind::InvalidInput` before reading anything if seabolt cannot hold `len`.
+    pub fn from_reader<R: Read>(r: &mut R, len: usize) -> io::Result<Self> {
+        let size = i32::try_from(len).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{} bytes does not fit in a seabolt value", len),
+            )
+        })?;
+        let value = Value::try_new().ok_or(BoltError::OutOfMemory)?;
+        if len == 0 {
+            return Ok(value.into_bytes(&mut []));
+        }
+        // Size the value without copying anything in, then read straight into its buffer,
+        // zeroed first so the reader never sees uninitialised memory.
+        let data = unsafe {
+            seabolt_sys::BoltValue_format_as_Bytes(value.ptr, ptr::null_mut(), size);
+            seabolt_sys::BoltBytes_get_all(value.ptr) as *mut u8
+        };
+        if data.is_null() {
+            return Err(BoltError::OutOfMemory.into());
+        }
+        let buf = unsafe {
+            ptr::write_bytes(data, 0, len);
+            slice::from_raw_parts_mut(data, len)
+        };
+        r.read_exact(buf)?;
+        Ok(value)
+    }
+
     // Structure
     pub fn into_structure(self, code: i16, fields: Vec<Value>) -> Self {
+        self.try_into_structure(code, fields)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// `into_structure`, but returning `BoltError::OutOfMemory` instead of panicking when
+    /// seabolt cannot allocate room for a field, or `BoltError::IntegerOutOfRange` when there
+    /// are more fields than seabolt can size.
+    pub fn try_into_structure(self, code: i16, fields: Vec<Value>) -> Result<Self, BoltError> {
+        let len = try_bolt_len(fields.len())?;
         unsafe {
-            seabolt_sys::BoltValue_format_as_Structure(self.ptr, code, fields.len() as i32);
+            seabolt_sys::BoltValue_format_as_Structure(self.ptr, code, len);
         }
 
         for (i, v) in fields.into_iter().enumerate() {
             let p = unsafe { seabolt_sys::BoltStructure_value(self.ptr, i as i32) };
-            unsafe { seabolt_sys::BoltValue_copy(v.ptr, p) };
+            v.copy_to(p)?;
         }
 
-        self
+        Ok(self)
     }
 
+    /// Deep-copies every field, as the values inside the structure stay owned by `self`.
     pub fn as_structure(&self) -> Structure {
         assert_eq!(self.get_type(), ValueType::Structure);
-        let size = unsafe { seabolt_sys::BoltValue_size(self.ptr) };
+        Structure {
+            code: self.structure_code(),
+            fields: (0..self.len())
+                .map(|i| self.structure_field(i).unwrap().clone())
+                .collect(),
+        }
+    }
 
-        let code = unsafe { seabolt_sys::BoltStructure_code(self.ptr) };
-        let mut fields = Vec::with_capacity(size as usize);
+    pub(crate) fn expect_structure(
+        &self,
+        kind: StructureKind,
+        arity: usize,
+    ) -> Result<(), BoltError> {
+        self.expect_type(ValueType::Structure)?;
+        let found = StructureKind::from_code(self.structure_code());
+        if found != kind {
+            return Err(BoltError::UnexpectedStructure {
+                expected: kind,
+                found,
+            });
+        }
+        if self.len() != arity {
+            return Err(BoltError::StructureArity {
+                kind,
+                expected: arity,
+                found: self.len(),
+            });
+        }
+        Ok(())
+    }
 
-        for i in 0..size {
-            let v = unsafe { Value::from_ptr(seabolt_sys::BoltStructure_value(self.ptr, i)) };
-            fields.push(v);
+    // BoltValue_copy reports nothing, so a copy cut short by a failed allocation is caught
+    // by checking that the destination took on the source's type and size.
+    pub(crate) fn copy_to(&self, dest: *mut seabolt_sys::BoltValue) -> Result<(), BoltError> {
+        unsafe { seabolt_sys::BoltValue_copy(self.ptr, dest) };
+        let copied = unsafe {
+            seabolt_sys::BoltValue_type(dest) == seabolt_sys::BoltValue_type(self.ptr)
+                && seabolt_sys::BoltValue_size(dest) == seabolt_sys::BoltValue_size(self.ptr)
+        };
+        if copied {
+            Ok(())
+        } else {
+            Err(BoltError::OutOfMemory)
         }
+    }
+
+    pub(crate) fn structure_code(&self) -> i16 {
+        unsafe { seabolt_sys::BoltStructure_code(self.ptr) }
+    }
+
+    // The value of the `i`th dictionary entry, for when the key is read with `key_bytes`.
+    pub(crate) fn dict_value(&self, i: usize) -> Option<ValueRef<'_>> {
+        if self.get_type() != ValueType::Dictionary || i >= self.len() {
+            return None;
+        }
+        let p = unsafe { seabolt_sys::BoltDictionary_value(self.ptr, i as i32) };
+        NonNull::new(p).map(NTTWrapper::new)
+    }
+
+    // The string stored under `key`. Like `key_bytes` it is read straight out of the
+    // dictionary, so it borrows from `self` rather than from the `ValueRef` `get` returns.
+    pub(crate) fn get_str(&self, key: &str) -> Option<&str> {
+        let v = self.get(key)?;
+        if v.get_type() != ValueType::String {
+            return None;
+        }
+        let bytes = unsafe {
+            raw_slice(
+                seabolt_sys::BoltString_get(v.ptr),
+                seabolt_sys::BoltValue_size(v.ptr),
+            )
+        };
+        str::from_utf8(bytes).ok()
+    }
+
+    pub(crate) fn structure_field(&self, i: usize) -> Option<ValueRef<'_>> {
+        if self.get_type() != ValueType::Structure || i >= self.len() {
+            return None;
+        }
+        let p = unsafe { seabolt_sys::BoltStructure_value(self.ptr, i as i32) };
+        NonNull::new(p).map(NTTWrapper::new)
+    }
+}
+
+// Floats compare and hash by bit pattern, so that equality is reflexive and Value can be
+// Eq. NaN equals a NaN with the same bits, and 0.0 does not equal -0.0. A value of a type
+// seabolt does not recognise can't be compared by content, so it only equals itself.
+impl PartialEq for Value {
+    fn eq(&self, other: &Value) -> bool {
+        let t = self.get_type();
+        if t != other.get_type() {
+            return false;
+        }
+        match t {
+            ValueType::Null => true,
+            ValueType::Boolean => self.as_boolean() == other.as_boolean(),
+            ValueType::Integer => self.as_integer() == other.as_integer(),
+            ValueType::Float => self.as_float().to_bits() == other.as_float().to_bits(),
+            ValueType::String => self.as_str_bytes() == other.as_str_bytes(),
+            ValueType::Bytes => self.as_bytes() == other.as_bytes(),
+            ValueType::List => {
+                self.len() == other.len()
+                    && (0..self.len())
+                        .all(|i| *self.list_get(i).unwrap() == *other.list_get(i).unwrap())
+            }
+            ValueType::Dictionary => {
+                self.len() == other.len()
+                    && self
+                        .iter_dict()
+                        .all(|(k, v)| other.get(k).map_or(false, |o| *o == *v))
+            }
+            ValueType::Structure => {
+                self.structure_code() == other.structure_code()
+                    && self.len() == other.len()
+                    && (0..self.len()).all(|i| {
+                        *self.structure_field(i).unwrap() == *other.structure_field(i).unwrap()
+                    })
+            }
+            ValueType::Unknown => self.ptr == other.ptr,
+        }
+    }
+}
+
+impl Eq for Value {}
+
+// Only scalars of the same type are ordered; mixed types give `None`, and lists,
+// dictionaries, bytes and structures give `Some(Equal)` when `==` holds and `None` otherwise.
+// Strings are ordered by their bytes, which for valid UTF-8 is the same as `str` ordering, so
+// that strings built with `into_raw_string` compare too. A NaN is likewise only `Some(Equal)`
+// to a NaN with the same bits, and -0.0 sorts below 0.0, both to agree with the bitwise
+// equality above.
+impl PartialOrd for Value {
+    fn partial_cmp(&self, other: &Value) -> Option<Ordering> {
+        let t = self.get_type();
+        if t != other.get_type() {
+            return None;
+        }
+        match t {
+            ValueType::Null => Some(Ordering::Equal),
+            ValueType::Boolean => self.as_boolean().partial_cmp(&other.as_boolean()),
+            ValueType::Integer => self.as_integer().partial_cmp(&other.as_integer()),
+            ValueType::Float => {
+                let (a, b) = (self.as_float(), other.as_float());
+                if a.is_nan() || b.is_nan() {
+                    if a.to_bits() == b.to_bits() {
+                        Some(Ordering::Equal)
+                    } else {
+                        None
+                    }
+                } else {
+                    Some(a.total_cmp(&b))
+                }
+            }
+            ValueType::String => self.as_str_bytes().partial_cmp(other.as_str_bytes()),
+            _ if self == other => Some(Ordering::Equal),
+            _ => None,
+        }
+    }
+}
+
+// Dictionaries hash order-independently by combining per-entry hashes with XOR.
+impl Hash for Value {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let t = self.get_type();
+        t.hash(state);
+        match t {
+            ValueType::Null | ValueType::Unknown => {}
+            ValueType::Boolean => self.as_boolean().hash(state),
+            ValueType::Integer => self.as_integer().hash(state),
+            ValueType::Float => self.as_float().to_bits().hash(state),
+            ValueType::String => self.as_str_bytes().hash(state),
+            ValueType::Bytes => self.as_bytes().hash(state),
+            ValueType::List => {
+                self.len().hash(state);
+                for i in 0..self.len() {
+                    self.list_get(i).unwrap().hash(state);
+                }
+            }
+            ValueType::Dictionary => {
+                self.len().hash(state);
+                let mut acc = 0_u64;
+                for (k, v) in self.iter_dict() {
+                    let mut h = DefaultHasher::new();
+                    k.hash(&mut h);
+                    v.hash(&mut h);
+                    acc ^= h.finish();
+                }
+                acc.hash(state);
+            }
+            ValueType::Structure => {
+                self.structure_code().hash(state);
+                self.len().hash(state);
+                for i in 0..self.len() {
+                    self.structure_field(i).unwrap().hash(state);
+                }
+            }
+        }
+    }
+}
+
+// seabolt sizes strings and containers as i32, so a longer one would silently wrap around.
+fn try_bolt_len(len: usize) -> Result<i32, BoltError> {
+    i32::try_from(len).map_err(|_| BoltError::IntegerOutOfRange {
+        value: len as i128,
+        target: "a seabolt length",
+    })
+}
+
+// For the builders that have no way to report an error.
+fn bolt_len(len: usize) -> i32 {
+    try_bolt_len(len).unwrap_or_else(|e| panic!("{}", e))
+}
+
+// seabolt may hand back a null pointer for an empty string.
+unsafe fn raw_slice<'a>(p: *const c_char, len: i32) -> &'a [u8] {
+    if p.is_null() || len <= 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(p as *const u8, len as usize)
+    }
+}
+
+macro_rules! value_from {
+    ($ctor:ident, $($t:ty),+) => {
+        $(impl From<$t> for Value {
+            fn from(v: $t) -> Self {
+                Value::$ctor(v)
+            }
+        })+
+    };
+}
+
+value_from!(from_boolean, bool);
+value_from!(from_integer, i8, i16, i32, i64, u8, u16, u32);
+value_from!(from_float, f32, f64);
+value_from!(from_string, &str, String);
+value_from!(from_list, Vec<Value>);
+
+/// Builds a list from mixed-type elements, converting each with `Value::from`, e.g.
+/// `value_list![1, "two", 3.0, true]`.
+#[macro_export]
+macro_rules! value_list {
+    ($($v:expr),* $(,)?) => {
+        $crate::Value::from_list(::std::vec![$($crate::Value::from($v)),*])
+    };
+}
+
+/// Builds a dictionary, converting each value with `Value::from`, e.g.
+/// `value_map! { "name" => "Bob", "age" => 42 }`. As with `into_dict`, the last entry wins
+/// when a key repeats.
+#[macro_export]
+macro_rules! value_map {
+    ($($k:expr => $v:expr),* $(,)?) => {
+        $crate::Value::from_dict(::std::vec![
+            $((::std::string::ToString::to_string(&$k), $crate::Value::from($v))),*
+        ])
+    };
+}
+
+/// Builds a dictionary; as with `into_dict`, the last entry wins when a key repeats.
+impl From<&[(&str, Value)]> for Value {
+    fn from(entries: &[(&str, Value)]) -> Self {
+        Value::from_dict(entries.iter().map(|(k, v)| (k.to_string(), v.clone())))
+    }
+}
+
+/// Builds a dictionary; as with `into_dict`, the last entry wins when a key repeats.
+impl From<Vec<(&str, Value)>> for Value {
+    fn from(entries: Vec<(&str, Value)>) -> Self {
+        Value::from_dict(entries.into_iter().map(|(k, v)| (k.to_string(), v)))
+    }
+}
+
+impl FromIterator<Value> for Value {
+    fn from_iter<I: IntoIterator<Item = Value>>(iter: I) -> Self {
+        Value::from_list(iter)
+    }
+}
+
+/// Collects into a dictionary; as with `into_dict`, the last entry wins when a key repeats.
+impl FromIterator<(String, Value)> for Value {
+    fn from_iter<I: IntoIterator<Item = (String, Value)>>(iter: I) -> Self {
+        Value::from_dict(iter)
+    }
+}
+
+impl TryFrom<&Value> for bool {
+    type Error = BoltError;
+
+    fn try_from(v: &Value) -> Result<Self, BoltError> {
+        v.expect_type(ValueType::Boolean)?;
+        Ok(v.as_boolean())
+    }
+}
+
+impl TryFrom<&Value> for i64 {
+    type Error = BoltError;
 
-        Structure { code, fields }
+    fn try_from(v: &Value) -> Result<Self, BoltError> {
+        v.expect_type(ValueType::Integer)?;
+        Ok(v.as_integer())
+    }
+}
+
+// Integers widen to floats, but floats are never narrowed to integers.
+impl TryFrom<&Value> for f64 {
+    type Error = BoltError;
+
+    fn try_from(v: &Value) -> Result<Self, BoltError> {
+        match v.get_type() {
+            ValueType::Float => Ok(v.as_float()),
+            ValueType::Integer => Ok(v.as_integer() as f64),
+            found => Err(BoltError::TypeMismatch {
+                expected: ValueType::Float,
+                found,
+            }),
+        }
+    }
+}
+
+impl TryFrom<&Value> for String {
+    type Error = BoltError;
+
+    fn try_from(v: &Value) -> Result<Self, BoltError> {
+        v.expect_type(ValueType::String)?;
+        Ok(v.as_string().to_string())
+    }
+}
+
+macro_rules! try_from_owned {
+    ($($t:ty),+) => {
+        $(impl TryFrom<Value> for $t {
+            type Error = BoltError;
+
+            fn try_from(v: Value) -> Result<Self, BoltError> {
+                <$t>::try_from(&v)
+            }
+        })+
+    };
+}
+
+try_from_owned!(bool, i64, f64, String);
+
+macro_rules! as_narrow {
+    ($($name:ident => $t:ty),+ $(,)?) => {
+        impl Value {
+            $(/// Reads an integer that must fit in the narrower type, rather than truncating it.
+            pub fn $name(&self) -> Result<$t, BoltError> {
+                let v = i64::try_from(self)?;
+                <$t>::try_from(v).map_err(|_| BoltError::IntegerOutOfRange {
+                    value: v.into(),
+                    target: stringify!($t),
+                })
+            })+
+        }
+    };
+}
+
+as_narrow!(
+    as_i8 => i8,
+    as_i16 => i16,
+    as_i32 => i32,
+    as_u8 => u8,
+    as_u16 => u16,
+    as_u32 => u32,
+    as_u64 => u64,
+    as_usize => usize,
+);
+
+impl Clone for Value {
+    fn clone(&self) -> Self {
+        unsafe { Value::duplicate(self.ptr) }.expect("BoltValue_duplicate failed to allocate")
     }
 }
 
@@ -266,3 +1130,196 @@ impl Drop for Value {
         }
     }
 }
+
+// A Value uniquely owns its BoltValue, and seabolt values carry no thread-local state.
+unsafe impl Send for Value {}
+
+/// An immutable `Value` behind an `Arc`, for sharing a result (e.g. a cached lookup table)
+/// between threads without copying it. Reading goes through `Deref`; to change it, copy it
+/// back out with `to_value`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct SharedValue(Arc<Value>);
+
+impl SharedValue {
+    pub fn to_value(&self) -> Value {
+        (*self.0).clone()
+    }
+}
+
+impl Deref for SharedValue {
+    type Target = Value;
+
+    fn deref(&self) -> &Value {
+        &self.0
+    }
+}
+
+// Every `&Value` method only reads the BoltValue, so concurrent readers are safe once
+// nothing can take the value back out to mutate it.
+unsafe impl Send for SharedValue {}
+unsafe impl Sync for SharedValue {}
+
+impl Value {
+    pub fn into_shared(self) -> SharedValue {
+        SharedValue(Arc::new(self))
+    }
+}
+
+impl NTTWrap for Value {
+    type ptr = seabolt_sys::BoltValue;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn as_list_outlives_parent() {
+        let list = value_list![1, "two", value_list![3]];
+        let items = list.as_list();
+        drop(list);
+        assert_eq!(items[1].as_string(), "two");
+        assert_eq!(items[2].as_list()[0].as_integer(), 3);
+    }
+
+    #[test]
+    fn as_structure_outlives_parent() {
+        let v = Value::new().into_structure(0x58, vec![Value::from(1), Value::from("x")]);
+        let s = v.as_structure();
+        drop(v);
+        assert_eq!(s.code, 0x58);
+        assert_eq!(s.fields[1].as_string(), "x");
+    }
+
+    #[test]
+    fn try_into_builders() {
+        let entries = vec![("a".to_string(), Value::from(1))];
+        let dict = Value::new().try_into_dict(entries).unwrap();
+        assert_eq!(dict.get("a").unwrap().as_integer(), 1);
+        let list = Value::new().try_into_list(vec![dict.clone()]).unwrap();
+        assert_eq!(*list.list_get(0).unwrap(), dict);
+        let s = Value::new().try_into_structure(0x58, vec![list]).unwrap();
+        assert_eq!(s.structure_field(0).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn structure_kind_and_equality() {
+        let point = |x: f64| {
+            let fields = vec![Value::from(7203), Value::from(x), Value::from(1.0)];
+            Value::new().into_structure(0x58, fields)
+        };
+        let a = point(0.5).as_structure();
+        assert_eq!(a.kind(), StructureKind::Point2D);
+        assert_eq!(a, point(0.5).as_structure());
+        assert_ne!(a, point(1.5).as_structure());
+
+        let unknown = Value::new().into_structure(0x01, Vec::new()).as_structure();
+        assert_eq!(unknown.kind(), StructureKind::Unknown(0x01));
+        assert_eq!(StructureKind::Unknown(0x01).code(), 0x01);
+    }
+
+    #[test]
+    fn get_path_two_levels() {
+        let v = value_map! {
+            "person" => value_map! { "address" => value_map! { "city" => "Cambridge" } },
+            "tags" => value_list!["a"],
+        };
+        let city = v.get_path(&["person", "address", "city"]).unwrap();
+        assert_eq!(city.as_string(), "Cambridge");
+        assert_eq!(*v.get_path(&[]).unwrap(), v);
+        assert!(v.get_path(&["person", "phone", "city"]).is_none());
+        assert!(v.get_path(&["tags", "a"]).is_none());
+        assert!(v.get_path(&["person", "address", "city", "name"]).is_none());
+    }
+
+    fn hash_of(v: &Value) -> u64 {
+        let mut h = DefaultHasher::new();
+        v.hash(&mut h);
+        h.finish()
+    }
+
+    #[test]
+    fn float_specials_round_trip() {
+        let quiet_nan = f64::from_bits(0x7ff8_0000_0000_0001);
+        for &f in &[f64::NAN, quiet_nan, f64::INFINITY, f64::NEG_INFINITY, -0.0] {
+            let v = Value::from(f);
+            assert_eq!(v.as_float().to_bits(), f.to_bits());
+            assert_eq!(v.clone().as_float().to_bits(), f.to_bits());
+        }
+    }
+
+    #[test]
+    fn float_specials_compare_by_bits() {
+        let nan = Value::from(f64::NAN);
+        assert_eq!(nan, Value::from(f64::NAN));
+        assert_eq!(hash_of(&nan), hash_of(&Value::from(f64::NAN)));
+        let other_nan = Value::from(f64::from_bits(0x7ff8_0000_0000_0001));
+        assert_ne!(nan, other_nan);
+        assert_eq!(nan.partial_cmp(&nan), Some(Ordering::Equal));
+        assert_eq!(nan.partial_cmp(&other_nan), None);
+        assert_eq!(nan.partial_cmp(&Value::from(1.0)), None);
+
+        let list = value_list![1, 2];
+        assert_eq!(list.partial_cmp(&value_list![1, 2]), Some(Ordering::Equal));
+        assert_eq!(list.partial_cmp(&value_list![1, 3]), None);
+
+        let inf = Value::from(f64::INFINITY);
+        assert_eq!(inf, Value::from(f64::INFINITY));
+        assert_eq!(hash_of(&inf), hash_of(&Value::from(f64::INFINITY)));
+        assert_ne!(inf, Value::from(f64::NEG_INFINITY));
+        assert!(Value::from(f64::NEG_INFINITY) < inf);
+
+        assert_ne!(Value::from(0.0), Value::from(-0.0));
+        assert!(Value::from(-0.0) < Value::from(0.0));
+    }
+
+    #[test]
+    fn invalid_utf8_strings_compare_and_hash() {
+        let ff = Value::from_raw_string(b"\xff");
+        assert_eq!(ff, Value::from_raw_string(b"\xff"));
+        assert_ne!(ff, Value::from_raw_string(b"\xfe"));
+        assert_eq!(hash_of(&ff), hash_of(&Value::from_raw_string(b"\xff")));
+        assert!(Value::from_raw_string(b"\xfe") < ff);
+        assert!(Value::from("a") < Value::from("b"));
+
+        let set: HashSet<Value> = vec![ff.clone(), Value::from_raw_string(b"\xff")]
+            .into_iter()
+            .collect();
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn deep_size_grows_with_content() {
+        let small = value_map! { "name" => "Bob" };
+        let large: Value = (0..100)
+            .map(|i| (format!("key{}", i), Value::from("x".repeat(100))))
+            .collect();
+        assert!(small.deep_size_bytes() < large.deep_size_bytes());
+        assert!(large.deep_size_bytes() > 100 * 100);
+
+        let nested = value_map! { "inner" => small.clone() };
+        assert!(nested.deep_size_bytes() > small.deep_size_bytes());
+    }
+
+    #[test]
+    fn from_reader_fills_the_value() {
+        let value = Value::from_reader(&mut &b"abc"[..], 3).unwrap();
+        assert_eq!(value.as_bytes(), b"abc");
+        let empty = Value::from_reader(&mut &b""[..], 0).unwrap();
+        assert_eq!(empty.as_bytes(), b"");
+        let err = Value::from_reader(&mut &b"abc"[..], 4).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[cfg(target_pointer_width = "64")]
+    #[test]
+    fn oversized_lengths_are_errors() {
+        assert_eq!(try_bolt_len(i32::MAX as usize).unwrap(), i32::MAX);
+        assert!(matches!(
+            try_bolt_len(i32::MAX as usize + 1),
+            Err(BoltError::IntegerOutOfRange { .. })
+        ));
+    }
+}
@@ -0,0 +1,113 @@
+use crate::{Value, ValueType};
+
+/// Callbacks for `Value::walk`. Every method does nothing by default, so a visitor only
+/// overrides the ones it needs.
+pub trait ValueVisitor {
+    /// Anything that is not a list, dictionary or structure, including null and bytes.
+    fn visit_scalar(&mut self, _v: &Value) {}
+
+    fn enter_list(&mut self, _v: &Value) {}
+
+    fn leave_list(&mut self, _v: &Value) {}
+
+    fn enter_dict(&mut self, _v: &Value) {}
+
+    /// Called before the value stored under `key` is walked.
+    fn visit_key(&mut self, _key: &str) {}
+
+    fn leave_dict(&mut self, _v: &Value) {}
+
+    fn enter_structure(&mut self, _v: &Value) {}
+
+    fn leave_structure(&mut self, _v: &Value) {}
+}
+
+impl Value {
+    /// Walks the value depth-first, calling `visitor` on the way into and out of each
+    /// container and once for every scalar. Dictionary entries and list and structure
+    /// elements are visited in their stored order.
+    pub fn walk<V: ValueVisitor + ?Sized>(&self, visitor: &mut V) {
+        match self.get_type() {
+            ValueType::List => {
+                visitor.enter_list(self);
+                for i in 0..self.len() {
+                    self.list_get(i).unwrap().walk(visitor);
+                }
+                visitor.leave_list(self);
+            }
+            ValueType::Dictionary => {
+                visitor.enter_dict(self);
+                for (k, v) in self.iter_dict() {
+                    visitor.visit_key(k);
+                    v.walk(visitor);
+                }
+                visitor.leave_dict(self);
+            }
+            ValueType::Structure => {
+                visitor.enter_structure(self);
+                for i in 0..self.len() {
+                    self.structure_field(i).unwrap().walk(visitor);
+                }
+                visitor.leave_structure(self);
+            }
+            _ => visitor.visit_scalar(self),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{diff::describe, value_list, value_map};
+
+    // Records every scalar, masking anything stored under a `password` key.
+    #[derive(Default)]
+    struct Redact {
+        keys: Vec<Option<String>>,
+        scalars: Vec<String>,
+        containers: usize,
+    }
+
+    impl ValueVisitor for Redact {
+        fn visit_scalar(&mut self, v: &Value) {
+            let masked = matches!(self.keys.last(), Some(Some(k)) if k == "password");
+            self.scalars.push(if masked {
+                "***".to_string()
+            } else {
+                describe(v)
+            });
+        }
+
+        fn enter_list(&mut self, _v: &Value) {
+            self.containers += 1;
+        }
+
+        fn enter_dict(&mut self, _v: &Value) {
+            self.containers += 1;
+            self.keys.push(None);
+        }
+
+        fn visit_key(&mut self, key: &str) {
+            *self.keys.last_mut().unwrap() = Some(key.to_string());
+        }
+
+        fn leave_dict(&mut self, _v: &Value) {
+            self.keys.pop();
+        }
+    }
+
+    #[test]
+    fn redacts_nested_passwords() {
+        let v = value_map! {
+            "user" => "alice",
+            "password" => "hunter2",
+            "accounts" => value_list![value_map! { "password" => "s3cret", "id" => 1 }, 2],
+        };
+        let mut redact = Redact::default();
+        v.walk(&mut redact);
+        redact.scalars.sort();
+        assert_eq!(redact.scalars, ["\"alice\"", "***", "***", "1", "2"]);
+        assert_eq!(redact.containers, 3);
+        assert!(redact.keys.is_empty());
+    }
+}
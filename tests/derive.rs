@@ -0,0 +1,74 @@
+//! `#[derive(FromRecord)]` against rows scripted with `MockConnection`, so no server is needed.
+#![cfg(all(feature = "derive", feature = "mock"))]
+
+use seabolt::{BoltError, ConnectionLike, FromRecord, MockConnection, Record, Value, ValueType};
+
+#[derive(Debug, PartialEq, FromRecord)]
+struct Person {
+    name: String,
+    age: i64,
+    nick: Option<String>,
+}
+
+fn rows(fields: &[&str], row: Vec<Value>) -> Vec<Record> {
+    let conn = MockConnection::build()
+        .with_query("MATCH (p) RETURN p", fields, vec![row])
+        .finish();
+    conn.run_collect("MATCH (p) RETURN p", Value::from_null())
+        .unwrap()
+        .0
+}
+
+#[test]
+fn reads_each_field_from_its_column() {
+    let records = rows(
+        &["name", "age", "nick"],
+        vec![Value::from("Bob"), Value::from(42), Value::from("bobby")],
+    );
+    assert_eq!(
+        Person::from_record(&records[0]).unwrap(),
+        Person {
+            name: "Bob".to_string(),
+            age: 42,
+            nick: Some("bobby".to_string()),
+        }
+    );
+}
+
+#[test]
+fn option_fields_accept_null_and_missing_columns() {
+    let null = rows(
+        &["name", "age", "nick"],
+        vec![Value::from("Bob"), Value::from(42), Value::from_null()],
+    );
+    let missing = rows(&["name", "age"], vec![Value::from("Bob"), Value::from(42)]);
+    for records in &[null, missing] {
+        assert_eq!(Person::from_record(&records[0]).unwrap().nick, None);
+    }
+}
+
+#[test]
+fn missing_column_is_an_error() {
+    let records = rows(&["name"], vec![Value::from("Bob")]);
+    assert_eq!(
+        Person::from_record(&records[0]),
+        Err(BoltError::MissingColumn {
+            name: "age".to_string()
+        })
+    );
+}
+
+#[test]
+fn mismatched_column_is_an_error() {
+    let records = rows(
+        &["name", "age"],
+        vec![Value::from("Bob"), Value::from("42")],
+    );
+    assert_eq!(
+        Person::from_record(&records[0]),
+        Err(BoltError::TypeMismatch {
+            expected: ValueType::Integer,
+            found: ValueType::String,
+        })
+    );
+}
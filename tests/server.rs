@@ -0,0 +1,229 @@
+//! Tests against a live Neo4j at localhost:7687 with the credentials neo4j/password, as in
+//! the examples. They are ignored by default; run them with `cargo test -- --ignored`.
+
+use std::{
+    sync::{Arc, Mutex},
+    thread,
+};
+
+use seabolt::{
+    basic_auth, AccessMode, Address, Bolt, BoltError, Config, Connection, Connector, Driver,
+    Params, Value,
+};
+
+// Only one Bolt may be initialized at a time, so the tests take turns.
+static BOLT: Mutex<()> = Mutex::new(());
+
+fn with_connector<F: FnOnce(&Connector)>(f: F) {
+    let _guard = BOLT.lock().unwrap_or_else(|e| e.into_inner());
+    let bolt = Bolt::init().unwrap();
+    let address = Address::new("localhost", "7687");
+    let auth = basic_auth("neo4j", "password", None);
+    let config = Config::build().finish();
+    let connector = bolt.create_connector(&address, &auth, &config);
+    f(&connector);
+}
+
+fn count(conn: &Connection, label: &str) -> i64 {
+    let cypher = format!("MATCH (n:{}) RETURN count(n) AS n", label);
+    let record = conn.run_single(&cypher, Value::from_null()).unwrap();
+    record.get_as("n").unwrap()
+}
+
+#[test]
+#[ignore]
+fn empty_result() {
+    with_connector(|connector| {
+        let conn = connector.acquire(AccessMode::Read).unwrap();
+        let mut records = conn
+            .run("MATCH (n:DoesNotExist) RETURN n", Value::from_null())
+            .unwrap();
+        assert_eq!(records.fields(), ["n"]);
+        assert!(records.next().is_none());
+        let summary = records.consume().unwrap();
+        assert!(!summary.counters().contains_updates);
+        assert!(summary.notifications().is_empty());
+
+        // The connection is left ready for the next query.
+        let record = conn
+            .run_single("RETURN 1 AS one", Value::from_null())
+            .unwrap();
+        assert_eq!(record.get("one").unwrap().as_integer(), 1);
+    });
+}
+
+#[test]
+#[ignore]
+fn float_specials_round_trip() {
+    with_connector(|connector| {
+        let conn = connector.acquire(AccessMode::Read).unwrap();
+        for &f in &[f64::NAN, f64::INFINITY, f64::NEG_INFINITY, -0.0] {
+            let params = Params::new().insert("x", f).build();
+            let record = conn.run_single("RETURN $x AS x", params).unwrap();
+            let x = record.get("x").unwrap().as_float();
+            assert_eq!(x.to_bits(), f.to_bits());
+        }
+    });
+}
+
+#[test]
+#[ignore]
+fn pool_metrics_track_checkouts() {
+    with_connector(|connector| {
+        assert_eq!(connector.pool_metrics().in_use, 0);
+        let first = connector.acquire(AccessMode::Read).unwrap();
+        let second = connector.acquire(AccessMode::Read).unwrap();
+        assert_eq!(connector.pool_metrics().in_use, 2);
+        drop(first);
+        let metrics = connector.pool_metrics();
+        assert_eq!(metrics.in_use, 1);
+        assert_eq!(metrics.peak_in_use, 2);
+        second.close().unwrap();
+        let metrics = connector.pool_metrics();
+        assert_eq!(metrics.in_use, 0);
+        assert_eq!(metrics.peak_in_use, 2);
+    });
+}
+
+#[test]
+#[ignore]
+fn reset_after_syntax_error() {
+    with_connector(|connector| {
+        let conn = connector.acquire(AccessMode::Read).unwrap();
+        match conn.run("THIS IS NOT CYPHER", Value::from_null()) {
+            Err(BoltError::Server { code, .. }) => {
+                assert_eq!(code, "Neo.ClientError.Statement.SyntaxError")
+            }
+            other => panic!("expected a syntax error, got {:?}", other.map(|_| ())),
+        }
+        conn.reset().unwrap();
+        let record = conn
+            .run_single("RETURN 1 AS one", Value::from_null())
+            .unwrap();
+        assert_eq!(record.get("one").unwrap().as_integer(), 1);
+    });
+}
+
+#[test]
+#[ignore]
+fn rollback_leaves_no_writes() {
+    with_connector(|connector| {
+        let conn = connector.acquire(AccessMode::Write).unwrap();
+        let before = count(&conn, "RollbackTest");
+        conn.begin(None).unwrap();
+        conn.run("CREATE (:RollbackTest)", Value::from_null())
+            .unwrap()
+            .consume()
+            .unwrap();
+        assert_eq!(count(&conn, "RollbackTest"), before + 1);
+        conn.rollback().unwrap();
+        assert_eq!(count(&conn, "RollbackTest"), before);
+    });
+}
+
+#[test]
+#[ignore]
+fn discard_keeps_counters() {
+    with_connector(|connector| {
+        let conn = connector.acquire(AccessMode::Write).unwrap();
+        conn.begin(None).unwrap();
+        let summary = conn
+            .run_discard(
+                "UNWIND range(1, 3) AS i CREATE (n:DiscardTest {i: i}) RETURN n",
+                Value::from_null(),
+            )
+            .unwrap();
+        let counters = summary.counters();
+        assert_eq!(counters.nodes_created, 3);
+        assert_eq!(counters.properties_set, 3);
+        assert!(counters.contains_updates);
+        assert_eq!(count(&conn, "DiscardTest"), 3);
+        conn.rollback().unwrap();
+    });
+}
+
+#[test]
+#[ignore]
+fn ping_keeps_transaction() {
+    with_connector(|connector| {
+        let mut conn = connector.acquire(AccessMode::Write).unwrap();
+        let before = count(&conn, "PingTest");
+        conn.begin(None).unwrap();
+        conn.run("CREATE (:PingTest)", Value::from_null())
+            .unwrap()
+            .consume()
+            .unwrap();
+        conn.ping().unwrap();
+        assert_eq!(count(&conn, "PingTest"), before + 1);
+        conn.rollback().unwrap();
+    });
+}
+
+#[test]
+#[ignore]
+fn pipeline_with_bad_params_sends_nothing() {
+    with_connector(|connector| {
+        let conn = connector.acquire(AccessMode::Write).unwrap();
+        let before = count(&conn, "PipelineTest");
+        let queries = [
+            ("CREATE (:PipelineTest)", Value::from_null()),
+            ("RETURN $x", Value::from(1)),
+        ];
+        assert!(matches!(
+            conn.run_pipeline(&queries),
+            Err(BoltError::TypeMismatch { .. })
+        ));
+        assert_eq!(count(&conn, "PipelineTest"), before);
+    });
+}
+
+#[test]
+#[ignore]
+fn connector_is_shared_between_threads() {
+    let _guard = BOLT.lock().unwrap_or_else(|e| e.into_inner());
+    let driver = Driver::builder("bolt://localhost:7687")
+        .with_basic_auth("neo4j", "password")
+        .build()
+        .unwrap();
+    let threads: Vec<_> = (0..4)
+        .map(|_| {
+            let connector = Arc::clone(driver.connector());
+            thread::spawn(move || {
+                let conn = connector.acquire(AccessMode::Read).unwrap();
+                let record = conn
+                    .run_single("RETURN 1 AS one", Value::from_null())
+                    .unwrap();
+                record.get("one").unwrap().as_integer()
+            })
+        })
+        .collect();
+    for t in threads {
+        assert_eq!(t.join().unwrap(), 1);
+    }
+    assert_eq!(driver.connector().pool_metrics().in_use, 0);
+}
+
+#[cfg(feature = "tokio")]
+#[test]
+#[ignore]
+fn acquire_and_run_async() {
+    let _guard = BOLT.lock().unwrap_or_else(|e| e.into_inner());
+    let driver = Driver::builder("bolt://localhost:7687")
+        .with_basic_auth("neo4j", "password")
+        .build()
+        .unwrap();
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    let conn = runtime
+        .block_on(driver.connector().acquire_async(AccessMode::Read))
+        .unwrap();
+    // The connection shares the connector, so it outlives the driver.
+    drop(driver);
+    let (conn, rows, _) = runtime
+        .block_on(conn.run_async("RETURN 1 AS one".to_string(), Value::from_null()))
+        .unwrap();
+    assert_eq!(rows[0].get("one").unwrap().as_integer(), 1);
+    drop(conn);
+    drop(Bolt::init().unwrap());
+}